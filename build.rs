@@ -0,0 +1,27 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// With the `embed-inputs` feature, generates the list of all `res/*.txt` inputs and `res/*.toml` answer files
+/// as `include_str!` entries, which `fs_util` includes.
+fn main() {
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        println!("cargo:rerun-if-changed=build.rs");
+        return;
+    }
+    println!("cargo:rerun-if-changed=res");
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let mut names: Vec<String> = fs::read_dir(Path::new(&root).join("res")).unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.ends_with(".txt") || name.ends_with(".toml"))
+        .collect();
+    names.sort();
+
+    let mut code = String::from("pub(crate) static EMBEDDED_FILES: &[(&str, &str)] = &[\n");
+    for name in names {
+        let path = Path::new(&root).join("res").join(&name);
+        code.push_str(&format!("    ({:?}, include_str!({:?})),\n", format!("res/{name}"), path.display().to_string()));
+    }
+    code.push_str("];\n");
+    fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs"), code).unwrap();
+}
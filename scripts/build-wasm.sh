@@ -0,0 +1,9 @@
+#!/bin/sh
+# Builds the WebAssembly module of src/wasm.rs. With wasm-bindgen-cli installed it also generates the
+# JavaScript bindings into web/.
+set -e
+cd "$(dirname "$0")/.."
+cargo rustc --release --lib --crate-type cdylib --features wasm --target wasm32-unknown-unknown
+if command -v wasm-bindgen >/dev/null; then
+    wasm-bindgen --target web --out-dir web target/wasm32-unknown-unknown/release/advent_of_code_2023.wasm
+fi
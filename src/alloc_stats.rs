@@ -0,0 +1,120 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// Heap usage of one measured closure, only available with the `alloc-stats` feature.
+#[derive(Serialize, Deserialize, Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    /// Highest number of bytes that were live at the same time, above what was live before.
+    pub peak_bytes: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} allocations, {} bytes, peak {} bytes", self.allocations, self.allocated_bytes, self.peak_bytes)
+    }
+}
+
+/// Runs `f` and returns the allocations it made on the current thread, or `None` without the `alloc-stats` feature.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let (result, stats) = counting::measure(f);
+        return (result, Some(stats));
+    }
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        return (f(), None);
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    use crate::alloc_stats::AllocStats;
+
+    /// Counts per thread, so that tasks solved in parallel do not disturb each other. Memory freed by
+    /// another thread than the one allocating it lowers the live bytes of the freeing thread.
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+        static ALLOCATED_BYTES: Cell<u64> = const { Cell::new(0) };
+        static LIVE_BYTES: Cell<i64> = const { Cell::new(0) };
+        static PEAK_BYTES: Cell<i64> = const { Cell::new(0) };
+    }
+
+    fn record_allocation(size: usize) {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        let _ = ALLOCATED_BYTES.try_with(|allocated| allocated.set(allocated.get() + size as u64));
+        let _ = LIVE_BYTES.try_with(|live| {
+            live.set(live.get() + size as i64);
+            let _ = PEAK_BYTES.try_with(|peak| peak.set(peak.get().max(live.get())));
+        });
+    }
+
+    fn record_deallocation(size: usize) {
+        let _ = LIVE_BYTES.try_with(|live| live.set(live.get() - size as i64));
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            record_allocation(layout.size());
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            record_deallocation(layout.size());
+            System.dealloc(ptr, layout)
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            record_allocation(layout.size());
+            System.alloc_zeroed(layout)
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            record_deallocation(layout.size());
+            record_allocation(new_size);
+            System.realloc(ptr, layout, new_size)
+        }
+    }
+
+    pub fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+        let allocations = ALLOCATIONS.get();
+        let allocated_bytes = ALLOCATED_BYTES.get();
+        let live_bytes = LIVE_BYTES.get();
+        PEAK_BYTES.set(live_bytes);
+        let result = f();
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.get() - allocations,
+            allocated_bytes: ALLOCATED_BYTES.get() - allocated_bytes,
+            peak_bytes: (PEAK_BYTES.get() - live_bytes).max(0) as u64,
+        };
+        return (result, stats);
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::alloc_stats::counting::measure;
+
+        #[test]
+        fn counts_allocations_of_closure() {
+            let (_, stats) = measure(|| {
+                let first = vec![0u8; 1000];
+                drop(first);
+                vec![0u8; 600].len()
+            });
+            assert_eq!(stats.allocations, 2);
+            assert_eq!(stats.allocated_bytes, 1600);
+            assert_eq!(stats.peak_bytes, 1000)
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::fs_util::embedded;
+use crate::runner::{run_parallel, Task};
+
+pub const ANSWERS_FILE: &str = "res/answers.toml";
+
+/// Expected answers of the example inputs in the default input directory, see [crate::inputs::InputResolver::example_answers_path].
+pub const EXAMPLE_ANSWERS_FILE: &str = "res/example-answers.toml";
+
+/// Expected answers per day and part, read from the `[day.N]` tables of the answers file.
+#[derive(Default)]
+pub struct Answers {
+    expected: HashMap<(usize, usize), usize>,
+}
+
+impl Answers {
+    pub fn expected(&self, day: usize, part: usize) -> Option<usize> {
+        self.expected.get(&(day, part)).copied()
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Correct,
+    Wrong { expected: usize },
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "ok"),
+            Verdict::Wrong { expected } => write!(f, "MISMATCH, expected {expected}"),
+            Verdict::Unknown => write!(f, "no expected answer"),
+        }
+    }
+}
+
+pub fn check(answer: usize, expected: Option<usize>) -> Verdict {
+    match expected {
+        Some(expected) if expected == answer => Verdict::Correct,
+        Some(expected) => Verdict::Wrong { expected },
+        None => Verdict::Unknown,
+    }
+}
+
+/// Runs all tasks and prints how each answer compares to the expected one. A panicking task counts as a mismatch.
+/// Returns whether no answer contradicts the answers file.
+pub fn verify_tasks(tasks: &[Task], answers: &Answers) -> bool {
+    let mut mismatches = 0;
+    run_parallel(tasks, 1, None, |task, event| match event.into_record() {
+        Ok(record) => {
+            let verdict = check(record.answer, answers.expected(record.day, record.part));
+            if let Verdict::Wrong { .. } = verdict {
+                mismatches += 1;
+            }
+            println!("AoC {}.{}: {} {verdict}", record.day, record.part, record.answer);
+        }
+        Err(message) => {
+            mismatches += 1;
+            println!("AoC {} on {} failed: {message}", task.day.day(), task.input_file);
+        }
+    });
+    println!("{mismatches} mismatches");
+    return mismatches == 0;
+}
+
+static TABLE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^\[\s*day\.(?<day>\d+|"\d+")\s*\]$"#).unwrap());
+static ANSWER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^(?<part>part\d+|"part\d+")\s*=\s*(?<answer>\d+)$"#).unwrap());
+
+/// Reads an answers file, preferring the embedded copy if there is one. Errors name the file and, for content
+/// that cannot be parsed, the line.
+pub fn read_answers(file: impl AsRef<Path>) -> Result<Answers, String> {
+    let file = file.as_ref();
+    let data = match embedded(file) {
+        Some(data) => data.to_string(),
+        None => fs::read_to_string(file).map_err(|error| format!("cannot read {}: {error}", file.display()))?,
+    };
+    parse_answers(&data).map_err(|error| format!("{}: {error}", file.display()))
+}
+
+/// Parses the subset of TOML used by the answers files: `[day.N]` tables of `partM = ANSWER` keys, with comments.
+pub fn parse_answers(data: &str) -> Result<Answers, String> {
+    let mut expected = HashMap::new();
+    let mut day = None;
+    for (index, line) in data.lines().enumerate() {
+        match parse_line(line, index)? {
+            Line::Blank => {}
+            Line::Table(table_day) => day = Some(table_day),
+            Line::Answer { part, answer } => {
+                let Some(day) = day else {
+                    return Err(format!("line {}: answer outside of a [day.N] table: {}", index + 1, line.trim()));
+                };
+                expected.insert((day, part), answer);
+            }
+        }
+    }
+    return Ok(Answers {
+        expected
+    });
+}
+
+/// Sets the `(part, answer)` pairs of one day in the text of an answers file and keeps all other lines, comments
+/// included. Missing keys are added at the end of the `[day.N]` table, a missing table is inserted in day order.
+pub fn update_answers(data: &str, day: usize, answers: &[(usize, usize)]) -> Result<String, String> {
+    let mut lines: Vec<String> = data.lines().map(|line| line.to_string()).collect();
+    let parsed = data.lines().enumerate().map(|(index, line)| parse_line(line, index)).collect::<Result<Vec<Line>, String>>()?;
+    let tables: Vec<(usize, usize)> = parsed.iter().enumerate().filter_map(|(index, line)| match line {
+        Line::Table(table_day) => Some((index, *table_day)),
+        _ => None,
+    }).collect();
+    let Some(table) = tables.iter().position(|(_, table_day)| *table_day == day) else {
+        let mut inserted = vec![format!("[day.{day}]")];
+        inserted.extend(answers.iter().map(|(part, answer)| format!("part{part} = {answer}")));
+        match tables.iter().find(|(_, table_day)| *table_day > day) {
+            Some((index, _)) => {
+                inserted.push(String::new());
+                lines.splice(index..index, inserted);
+            }
+            None => {
+                if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                    lines.push(String::new());
+                }
+                lines.extend(inserted);
+            }
+        }
+        return Ok(lines.join("\n") + "\n");
+    };
+    let start = tables[table].0 + 1;
+    let mut end = tables.get(table + 1).map_or(lines.len(), |(index, _)| *index);
+    for (part, answer) in answers {
+        let existing = (start..end).find(|index| matches!(parsed.get(*index), Some(Line::Answer { part: existing, .. }) if existing == part));
+        match existing {
+            Some(index) => {
+                let comment = lines[index].find('#').map_or(String::new(), |comment| format!(" {}", &lines[index][comment..]));
+                lines[index] = format!("part{part} = {answer}{comment}");
+            }
+            None => {
+                let position = (start..end).rev().find(|index| !lines[*index].trim().is_empty()).map_or(start, |index| index + 1);
+                lines.insert(position, format!("part{part} = {answer}"));
+                end += 1;
+            }
+        }
+    }
+    return Ok(lines.join("\n") + "\n");
+}
+
+enum Line {
+    Blank,
+    Table(usize),
+    Answer { part: usize, answer: usize },
+}
+
+fn parse_line(line: &str, index: usize) -> Result<Line, String> {
+    let line = line.split('#').next().unwrap().trim();
+    if line.is_empty() {
+        return Ok(Line::Blank);
+    }
+    if let Some(matched_table) = TABLE_REGEX.captures(line) {
+        return Ok(Line::Table(parse_number(&matched_table["day"], index)?));
+    }
+    if let Some(matched_answer) = ANSWER_REGEX.captures(line) {
+        return Ok(Line::Answer {
+            part: parse_number(matched_answer["part"].trim_matches('"').trim_start_matches("part"), index)?,
+            answer: parse_number(&matched_answer["answer"], index)?,
+        });
+    }
+    return Err(format!("line {}: cannot parse {line}", index + 1));
+}
+
+fn parse_number(number: &str, index: usize) -> Result<usize, String> {
+    number.trim_matches('"').parse().map_err(|_| format!("line {}: {number} is out of range", index + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::answers::{ANSWERS_FILE, check, EXAMPLE_ANSWERS_FILE, parse_answers, read_answers, update_answers, Verdict, verify_tasks};
+    use crate::inputs::InputResolver;
+    use crate::runner::Task;
+    use crate::solution::{DAYS, find_day, PARTS};
+
+    #[test]
+    fn parses_answers_per_day_and_part() {
+        let answers = parse_answers("# comment\n[day.3]\npart1 = 4361 # checked\n\n[day.\"12\"]\n\"part2\" = 7\n").unwrap();
+        assert_eq!(answers.expected(3, 1), Some(4361));
+        assert_eq!(answers.expected(12, 2), Some(7));
+        assert_eq!(answers.expected(3, 2), None)
+    }
+
+    #[test]
+    fn reports_line_that_cannot_be_parsed() {
+        assert_eq!(parse_answers("[day.3]\npart1 = 4361\npart2 = \"467835\"\n").err(), Some("line 3: cannot parse part2 = \"467835\"".to_string()));
+        assert_eq!(parse_answers("\npart1 = 4361\n").err(), Some("line 2: answer outside of a [day.N] table: part1 = 4361".to_string()));
+        assert_eq!(parse_answers("[day.3]\npart1 = 99999999999999999999999\n").err(), Some("line 2: 99999999999999999999999 is out of range".to_string()))
+    }
+
+    #[test]
+    fn missing_answers_file_is_an_error() {
+        assert_eq!(read_answers("res/missing.toml").err().map(|error| error.starts_with("cannot read res/missing.toml: ")), Some(true))
+    }
+
+    #[test]
+    fn updates_answers_of_one_day_in_place() {
+        let data = "# header\n\n[day.3]\npart1 = 1 # guessed\n\n[day.12]\npart2 = 7\n";
+        assert_eq!(update_answers(data, 3, &[(1, 4361), (2, 467835)]).unwrap(),
+                   "# header\n\n[day.3]\npart1 = 4361 # guessed\npart2 = 467835\n\n[day.12]\npart2 = 7\n");
+        assert_eq!(update_answers(data, 5, &[(1, 35)]).unwrap(),
+                   "# header\n\n[day.3]\npart1 = 1 # guessed\n\n[day.5]\npart1 = 35\n\n[day.12]\npart2 = 7\n");
+        assert_eq!(update_answers(data, 13, &[(1, 2)]).unwrap(),
+                   "# header\n\n[day.3]\npart1 = 1 # guessed\n\n[day.12]\npart2 = 7\n\n[day.13]\npart1 = 2\n");
+        assert_eq!(update_answers("# header\n", 1, &[(1, 142)]).unwrap(), "# header\n\n[day.1]\npart1 = 142\n");
+        assert!(update_answers("part1 = x\n", 1, &[(1, 142)]).is_err())
+    }
+
+    #[test]
+    fn checks_answer_against_expectation() {
+        assert_eq!(check(42, Some(42)), Verdict::Correct);
+        assert_eq!(check(41, Some(42)), Verdict::Wrong { expected: 42 });
+        assert_eq!(check(41, None), Verdict::Unknown)
+    }
+
+    #[test]
+    fn panicking_day_counts_as_mismatch() {
+        let tasks = vec![
+            Task { day: find_day(8).unwrap(), parts: vec![1], input_file: "res/aoc1-1-example.txt".to_string() },
+            Task { day: find_day(2).unwrap(), parts: vec![1, 2], input_file: "res/aoc2-example.txt".to_string() },
+        ];
+        assert!(!verify_tasks(&tasks, &read_answers(EXAMPLE_ANSWERS_FILE).unwrap()));
+        assert!(verify_tasks(&tasks[1..], &read_answers(EXAMPLE_ANSWERS_FILE).unwrap()))
+    }
+
+    #[test]
+    fn answers_file_covers_every_registered_part() {
+        let answers = read_answers(ANSWERS_FILE).unwrap();
+        for day in DAYS {
+            // A freshly scaffolded day has an empty input and no answers yet.
+            if fs::read_to_string(InputResolver::new("res").real_path(day.day())).unwrap_or_default().trim().is_empty() {
+                continue;
+            }
+            for part in PARTS {
+                assert!(answers.expected(day.day(), part).is_some(), "missing answer for {}.{part}", day.day());
+            }
+        }
+    }
+}
@@ -1,14 +1,28 @@
-use crate::fs_util::read_or_panic;
+use crate::solution::Solution;
 
-pub fn aoc_1_1() -> usize {
-    return calculate_calibration_value(&read_or_panic("res/aoc1.txt"));
-}
+pub struct Aoc1;
+
+impl Solution for Aoc1 {
+    type Input = String;
 
-pub fn aoc_1_2() -> usize {
-    return calculate_calibration_value_from_data_with_spelled_numbers(&read_or_panic("res/aoc1.txt"));
+    fn day(&self) -> usize {
+        1
+    }
+
+    fn parse(&self, input_data: &str) -> String {
+        input_data.to_string()
+    }
+
+    fn part_1(&self, calibration_data: &String) -> usize {
+        calculate_calibration_value(calibration_data)
+    }
+
+    fn part_2(&self, calibration_data: &String) -> usize {
+        calculate_calibration_value_from_data_with_spelled_numbers(calibration_data)
+    }
 }
 
-fn calculate_calibration_value(calibration_data: &str) -> usize {
+pub fn calculate_calibration_value(calibration_data: &str) -> usize {
     let mut calibration_value = 0;
     for line in  calibration_data.lines() {
         let digits: Vec<char> = line.chars().filter(|c| c.is_numeric()).collect();
@@ -31,7 +45,7 @@ const DIGIT_DICTIONARY: [(&str, usize); 9] = [
     ("nine", 9),
 ];
 
-fn calculate_calibration_value_from_data_with_spelled_numbers(calibration_data: &str) -> usize {
+pub fn calculate_calibration_value_from_data_with_spelled_numbers(calibration_data: &str) -> usize {
     let mut calibration_value = 0;
     for line in  calibration_data.lines() {
         calibration_value += evaluate_line_with_spelled_numbers(line);
@@ -65,17 +79,17 @@ fn evaluate_line_with_spelled_numbers(line: &str) -> usize {
 #[cfg(test)]
 mod tests {
     use crate::aoc1::{calculate_calibration_value, calculate_calibration_value_from_data_with_spelled_numbers};
-    use crate::fs_util::read_or_panic;
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_1_1() {
-        let example_data = read_or_panic("res/aoc1-1-example.txt");
-        assert_eq!(calculate_calibration_value(&example_data), 142);
+        let example_data = example_input(1, 1);
+        assert_eq!(calculate_calibration_value(&example_data), example_answer(1, 1));
     }
 
     #[test]
     fn aoc_1_2() {
-        let example_data = read_or_panic("res/aoc1-1-example.txt");
-        assert_eq!(calculate_calibration_value_from_data_with_spelled_numbers(&example_data), 363);
+        let example_data = example_input(1, 2);
+        assert_eq!(calculate_calibration_value_from_data_with_spelled_numbers(&example_data), example_answer(1, 2));
     }
 }
@@ -3,16 +3,19 @@ use std::collections::HashMap;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 
-use crate::fs_util::read_or_panic;
+use crate::parse_util::parse_argument;
+use crate::solution::Solution;
 
-struct Game {
-    number: usize,
-    combinations: Vec<CubeCombination>,
+#[derive(Serialize)]
+pub struct Game {
+    pub number: usize,
+    pub combinations: Vec<CubeCombination>,
 }
 
 impl Game {
-    fn is_valid_for(&self, cubes_in_bag: &CubeCombination) -> bool {
+    pub fn is_valid_for(&self, cubes_in_bag: &CubeCombination) -> bool {
         self.combinations.iter().all(|combination|
             combination.red <= cubes_in_bag.red
                 && combination.green <= cubes_in_bag.green
@@ -21,47 +24,71 @@ impl Game {
     }
 }
 
-#[derive(Copy, Clone)]
-struct CubeCombination {
-    red: usize,
-    green: usize,
-    blue: usize,
+#[derive(Copy, Clone, Serialize)]
+pub struct CubeCombination {
+    pub red: usize,
+    pub green: usize,
+    pub blue: usize,
 }
 
-pub fn aoc_2_1() -> usize {
-    let combination_in_bag = CubeCombination {
-        red: 12,
-        green:13,
-        blue: 14
-    };
-    return parse_and_combine_valid_games(&combination_in_bag, "res/aoc2.txt");
-}
+pub const CUBES_IN_BAG: CubeCombination = CubeCombination {
+    red: 12,
+    green: 13,
+    blue: 14,
+};
 
-pub fn aoc_2_2() -> usize {
-    return parse_and_combine_power_of_min_combination_possible("res/aoc2.txt");
-}
+pub struct Aoc2;
 
-fn parse_and_combine_valid_games(cubes_in_bag: &CubeCombination, input_file: &str) -> usize {
-    let input_data = read_or_panic(input_file);
-    return combine_valid_games(cubes_in_bag, &parse_input_data(&input_data));
-}
+impl Solution for Aoc2 {
+    type Input = Vec<Game>;
 
-fn combine_valid_games(cubes_in_bag: &CubeCombination, games: &Vec<Game>) -> usize {
-    games.iter().filter(|game| game.is_valid_for(cubes_in_bag)).map(|game| game.number).sum()
+    fn day(&self) -> usize {
+        2
+    }
+
+    fn parse(&self, input_data: &str) -> Vec<Game> {
+        parse_input_data(input_data)
+    }
+
+    fn part_1(&self, games: &Vec<Game>) -> usize {
+        combine_valid_games(&CUBES_IN_BAG, games)
+    }
+
+    fn part_2(&self, games: &Vec<Game>) -> usize {
+        combine_power_of_min_combination_possible(games)
+    }
+
+    fn queries(&self) -> &'static [&'static str] {
+        &["valid RED GREEN BLUE"]
+    }
+
+    fn query(&self, games: &Vec<Game>, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["valid", red, green, blue] => {
+                let cubes_in_bag = CubeCombination {
+                    red: parse_argument(red)?,
+                    green: parse_argument(green)?,
+                    blue: parse_argument(blue)?,
+                };
+                let valid_games: Vec<String> = games.iter().filter(|game| game.is_valid_for(&cubes_in_bag)).map(|game| game.number.to_string()).collect();
+                Ok(format!("{} valid games adding up to {}: {}", valid_games.len(), combine_valid_games(&cubes_in_bag, games), valid_games.join(", ")))
+            }
+            _ => Err(format!("unknown query {}", query.join(" "))),
+        }
+    }
 }
 
-fn parse_and_combine_power_of_min_combination_possible(input_file: &str) -> usize {
-    let input_data = read_or_panic(input_file);
-    return combine_power_of_min_combination_possible(&parse_input_data(&input_data));
+pub fn combine_valid_games(cubes_in_bag: &CubeCombination, games: &[Game]) -> usize {
+    games.iter().filter(|game| game.is_valid_for(cubes_in_bag)).map(|game| game.number).sum()
 }
 
-fn combine_power_of_min_combination_possible(games: &Vec<Game>) -> usize {
+pub fn combine_power_of_min_combination_possible(games: &[Game]) -> usize {
     games.iter().map(|game| {
         power_of_min_combination_possible(&game.combinations)
     }).sum()
 }
 
-fn power_of_min_combination_possible(combinations: &Vec<CubeCombination>) -> usize {
+pub fn power_of_min_combination_possible(combinations: &[CubeCombination]) -> usize {
     let min_combination = combinations.iter().fold(CubeCombination{red: 0, green: 0, blue: 0},
         |current_min, combination| CubeCombination {
             red: max(current_min.red, combination.red),
@@ -72,10 +99,10 @@ fn power_of_min_combination_possible(combinations: &Vec<CubeCombination>) -> usi
     return min_combination.red * min_combination.green * min_combination.blue;
 }
 
-const GAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Game\s(?<number>\d+):(?<combinations>[^\n]*)(\n|$)").unwrap());
-const COMBINATION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<amount>\d+)\s(?<color>[a-z]+)").unwrap());
+static GAME_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Game\s(?<number>\d+):(?<combinations>[^\n]*)(\n|$)").unwrap());
+static COMBINATION_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<amount>\d+)\s(?<color>[a-z]+)").unwrap());
 
-fn parse_input_data(input_data: &str) -> Vec<Game> {
+pub fn parse_input_data(input_data: &str) -> Vec<Game> {
     return GAME_REGEX.captures_iter(input_data).map(|matched_game| {
         let combinations = matched_game.name("combinations").unwrap().as_str().split(";").map(|combination_entry| {
             let combination: HashMap<&str, usize> = COMBINATION_REGEX.captures_iter(combination_entry).map(|matched_combination| {
@@ -98,7 +125,8 @@ fn parse_input_data(input_data: &str) -> Vec<Game> {
 
 #[cfg(test)]
 mod tests {
-    use crate::aoc2::{CubeCombination, parse_and_combine_power_of_min_combination_possible, parse_and_combine_valid_games};
+    use crate::aoc2::{combine_power_of_min_combination_possible, combine_valid_games, CubeCombination, parse_input_data};
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_2_1() {
@@ -107,11 +135,13 @@ mod tests {
             green: 13,
             blue: 14,
         };
-        assert_eq!(parse_and_combine_valid_games(&cubes_in_bag, "res/aoc2-example.txt"), 8)
+        let games = parse_input_data(&example_input(2, 1));
+        assert_eq!(combine_valid_games(&cubes_in_bag, &games), example_answer(2, 1))
     }
 
     #[test]
     fn aoc_2_2() {
-        assert_eq!(parse_and_combine_power_of_min_combination_possible("res/aoc2-example.txt"), 2286)
+        let games = parse_input_data(&example_input(2, 2));
+        assert_eq!(combine_power_of_min_combination_possible(&games), example_answer(2, 2))
     }
 }
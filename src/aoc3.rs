@@ -2,34 +2,39 @@ use std::ops::Range;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 
-use crate::fs_util::read_or_panic;
+use crate::solution::Solution;
 
-struct Number {
-    number: usize,
-    position: Range<usize>,
+#[derive(Serialize)]
+pub struct Number {
+    pub number: usize,
+    pub position: Range<usize>,
 }
 
-struct Symbol {
-    symbol: char,
-    position: usize,
+#[derive(Serialize)]
+pub struct Symbol {
+    pub symbol: char,
+    pub position: usize,
 }
 
-struct Line {
-    numbers: Vec<Number>,
-    symbols: Vec<Symbol>,
+#[derive(Serialize)]
+pub struct Line {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
 }
 
-struct Schematic {
-    lines: Vec<Line>
+#[derive(Serialize)]
+pub struct Schematic {
+    pub lines: Vec<Line>
 }
 
-struct Gear {
-    ratio: usize
+pub struct Gear {
+    pub ratio: usize
 }
 
 impl Schematic {
-    fn find_parts(&self) -> Vec<&Number>{
+    pub fn find_parts(&self) -> Vec<&Number>{
         let empty_symbols_vec = Vec::new();
         let mut parts = Vec::new();
         let mut i = 0;
@@ -56,7 +61,7 @@ impl Schematic {
         return parts;
     }
 
-    fn find_gears(&self) -> Vec<Gear> {
+    pub fn find_gears(&self) -> Vec<Gear> {
         let empty_numbers_vec = Vec::new();
         let mut gears = Vec::new();
         let mut i = 0;
@@ -96,27 +101,39 @@ impl Schematic {
     }
 }
 
-pub fn aoc_3_1() -> usize {
-    return parse_and_combine_part_numbers("res/aoc3.txt");
-}
+pub struct Aoc3;
+
+impl Solution for Aoc3 {
+    type Input = Schematic;
 
-pub fn aoc_3_2() -> usize {
-    return parse_and_combine_gear_ratios("res/aoc3.txt");
+    fn day(&self) -> usize {
+        3
+    }
+
+    fn parse(&self, input_data: &str) -> Schematic {
+        parse_input_data(input_data)
+    }
+
+    fn part_1(&self, schematic: &Schematic) -> usize {
+        combine_part_numbers(schematic)
+    }
+
+    fn part_2(&self, schematic: &Schematic) -> usize {
+        combine_gear_ratios(schematic)
+    }
 }
 
-fn parse_and_combine_part_numbers(file: &str) -> usize {
-    let schematic = parse_input_data(&read_or_panic(file));
+pub fn combine_part_numbers(schematic: &Schematic) -> usize {
     return schematic.find_parts().iter().map(|part| part.number).sum();
 }
 
-fn parse_and_combine_gear_ratios(file: &str) -> usize {
-    let schematic = parse_input_data(&read_or_panic(file));
+pub fn combine_gear_ratios(schematic: &Schematic) -> usize {
     return schematic.find_gears().iter().map(|gear| gear.ratio).sum();
 }
 
-const PARTS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<number>\d+)|(?<symbol>[^1-9.\n])").unwrap());
+static PARTS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<number>\d+)|(?<symbol>[^1-9.\n])").unwrap());
 
-fn parse_input_data(input_data: &str) -> Schematic {
+pub fn parse_input_data(input_data: &str) -> Schematic {
     let lines = input_data.lines().map(|line| {
         let mut numbers = Vec::new();
         let mut symbols = Vec::new();
@@ -129,7 +146,7 @@ fn parse_input_data(input_data: &str) -> Schematic {
             }
             if let Some(symbol_match) = capture.name("symbol") {
                 symbols.push(Symbol {
-                    symbol: symbol_match.as_str().chars().nth(0).unwrap(),
+                    symbol: symbol_match.as_str().chars().next().unwrap(),
                     position: symbol_match.start(),
                 })
             }
@@ -146,15 +163,18 @@ fn parse_input_data(input_data: &str) -> Schematic {
 
 #[cfg(test)]
 mod tests {
-    use crate::aoc3::{parse_and_combine_gear_ratios, parse_and_combine_part_numbers};
+    use crate::aoc3::{combine_gear_ratios, combine_part_numbers, parse_input_data};
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_3_1() {
-        assert_eq!(parse_and_combine_part_numbers("res/aoc3-example.txt"), 4361)
+        let schematic = parse_input_data(&example_input(3, 1));
+        assert_eq!(combine_part_numbers(&schematic), example_answer(3, 1))
     }
 
     #[test]
     fn aoc_3_2() {
-        assert_eq!(parse_and_combine_gear_ratios("res/aoc3-example.txt"), 467835)
+        let schematic = parse_input_data(&example_input(3, 2));
+        assert_eq!(combine_gear_ratios(&schematic), example_answer(3, 2))
     }
 }
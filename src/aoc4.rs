@@ -1,18 +1,20 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 
-use crate::fs_util::read_or_panic;
 use crate::parse_util::parse_numbers;
+use crate::solution::Solution;
+use crate::trace::trace_event;
 
-#[derive(Clone)]
-struct Card {
-    number: usize,
-    winning_numbers: Vec<usize>,
-    own_numbers: Vec<usize>,
+#[derive(Clone, Serialize)]
+pub struct Card {
+    pub number: usize,
+    pub winning_numbers: Vec<usize>,
+    pub own_numbers: Vec<usize>,
 }
 
 impl Card {
-    fn points(&self) -> usize {
+    pub fn points(&self) -> usize {
         let matches = self.matches();
         return if matches > 0 {
             2usize.pow(matches as u32 - 1)
@@ -21,34 +23,46 @@ impl Card {
         }
     }
 
-    fn matches(&self) -> usize {
+    pub fn matches(&self) -> usize {
         self.winning_numbers.iter().filter(|number| self.own_numbers.contains(number)).collect::<Vec<_>>().len()
     }
 }
 
-pub fn aoc_4_1() -> usize {
-    return parse_and_sum_up_points("res/aoc4.txt");
-}
+pub struct Aoc4;
+
+impl Solution for Aoc4 {
+    type Input = Vec<Card>;
 
-pub fn aoc_4_2() -> usize {
-    return parse_and_count_all_cards_including_won_ones("res/aoc4.txt");
+    fn day(&self) -> usize {
+        4
+    }
+
+    fn parse(&self, input_data: &str) -> Vec<Card> {
+        parse_input_data(input_data)
+    }
+
+    fn part_1(&self, cards: &Vec<Card>) -> usize {
+        sum_up_points(cards)
+    }
+
+    fn part_2(&self, cards: &Vec<Card>) -> usize {
+        count_all_cards_including_won_ones(cards)
+    }
 }
 
-fn parse_and_sum_up_points(input_file: &str) -> usize {
-    let input_data = read_or_panic(input_file);
-    return parse_input_data(&input_data).iter().map(|card| card.points()).sum();
+pub fn sum_up_points(cards: &[Card]) -> usize {
+    return cards.iter().map(|card| card.points()).sum();
 }
 
-fn parse_and_count_all_cards_including_won_ones(input_file: &str) -> usize {
-    let input_data = read_or_panic(input_file);
-    let original_cards = parse_input_data(&input_data);
-    let mut all_cards: Vec<Card> = original_cards.iter().map(|card| card.clone()).collect();
+pub fn count_all_cards_including_won_ones(original_cards: &[Card]) -> usize {
+    let mut all_cards: Vec<Card> = original_cards.to_vec();
     let mut i = 0;
     while i < all_cards.len() {
         let card = all_cards[i].clone();
         let matches = card.matches();
         for n in 0..matches {
             if let Some(won_card) = original_cards.get(card.number + n) {
+                trace_event!(4, "copy", won_by = card.number, card = won_card.number, cards = all_cards.len() + 1);
                 all_cards.insert(i + 1, won_card.clone());
             }
         }
@@ -57,9 +71,9 @@ fn parse_and_count_all_cards_including_won_ones(input_file: &str) -> usize {
     return all_cards.len();
 }
 
-const CARD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Card\s+(?<number>\d+):\s*(?<winning_numbers>(\d+\s*)*)\|\s*(?<own_numbers>(\d+\s*)*)(\n|$)").unwrap());
+static CARD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Card\s+(?<number>\d+):\s*(?<winning_numbers>(\d+\s*)*)\|\s*(?<own_numbers>(\d+\s*)*)(\n|$)").unwrap());
 
-fn parse_input_data(input_data: &str) -> Vec<Card> {
+pub fn parse_input_data(input_data: &str) -> Vec<Card> {
     return CARD_REGEX.captures_iter(input_data).map(|matched_card| {
         Card {
             number: matched_card.name("number").unwrap().as_str().parse().unwrap(),
@@ -71,15 +85,18 @@ fn parse_input_data(input_data: &str) -> Vec<Card> {
 
 #[cfg(test)]
 mod tests {
-    use crate::aoc4::{parse_and_count_all_cards_including_won_ones, parse_and_sum_up_points};
+    use crate::aoc4::{count_all_cards_including_won_ones, parse_input_data, sum_up_points};
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_4_1() {
-        assert_eq!(parse_and_sum_up_points( "res/aoc4-example.txt"), 14)
+        let cards = parse_input_data(&example_input(4, 1));
+        assert_eq!(sum_up_points(&cards), example_answer(4, 1))
     }
 
     #[test]
     fn aoc_4_2() {
-        assert_eq!(parse_and_count_all_cards_including_won_ones( "res/aoc4-example.txt"), 31)
+        let cards = parse_input_data(&example_input(4, 2));
+        assert_eq!(count_all_cards_including_won_ones(&cards), example_answer(4, 2))
     }
 }
@@ -1,42 +1,44 @@
+use std::ops::Range;
+
 use once_cell::sync::Lazy;
 use regex::{Captures, Regex};
+use serde::Serialize;
 
-use crate::fs_util::read_or_panic;
-use crate::parse_util::parse_numbers;
-
-struct Almanac {
-    seeds: Vec<usize>,
-    map: AlmanacMap,
-}
+use crate::parse_util::{parse_argument, parse_numbers};
+use crate::solution::{Alternative, Solution};
+use crate::trace::trace_event;
 
-struct AlmanacWithSeedRange {
-    seeds: Vec<SeedRange>,
-    map: AlmanacMap,
+#[derive(Serialize)]
+pub struct Almanac {
+    pub seeds: Vec<usize>,
+    pub map: AlmanacMap,
 }
 
-struct SeedRange {
-    start: usize,
-    length: usize,
+pub struct SeedRange {
+    pub start: usize,
+    pub length: usize,
 }
 
-struct AlmanacMap {
-    seed_to_soil: Vec<AlmanacMapping>,
-    soil_to_fertilizer: Vec<AlmanacMapping>,
-    fertilizer_to_water: Vec<AlmanacMapping>,
-    water_to_light: Vec<AlmanacMapping>,
-    light_to_temperature: Vec<AlmanacMapping>,
-    temperature_to_humidity: Vec<AlmanacMapping>,
-    humidity_to_location: Vec<AlmanacMapping>,
+#[derive(Serialize)]
+pub struct AlmanacMap {
+    pub seed_to_soil: Vec<AlmanacMapping>,
+    pub soil_to_fertilizer: Vec<AlmanacMapping>,
+    pub fertilizer_to_water: Vec<AlmanacMapping>,
+    pub water_to_light: Vec<AlmanacMapping>,
+    pub light_to_temperature: Vec<AlmanacMapping>,
+    pub temperature_to_humidity: Vec<AlmanacMapping>,
+    pub humidity_to_location: Vec<AlmanacMapping>,
 }
 
-struct AlmanacMapping {
-    source: usize,
-    destination: usize,
-    length: usize
+#[derive(Serialize)]
+pub struct AlmanacMapping {
+    pub source: usize,
+    pub destination: usize,
+    pub length: usize
 }
 
 impl AlmanacMapping {
-    fn get(&self, source: &usize) -> Option<usize> {
+    pub fn get(&self, source: &usize) -> Option<usize> {
         if *source >= self.source && *source < (self.source + self.length) {
             let diff = source - self.source;
             return Some(self.destination + diff);
@@ -47,40 +49,76 @@ impl AlmanacMapping {
 }
 
 impl AlmanacMap {
-    fn find_location_for_seed(&self, seed: &usize) -> usize {
-        let soil = self.find_destination(&self.seed_to_soil, &seed);
-        let fertilizer = self.find_destination(&self.soil_to_fertilizer, &soil);
-        let water = self.find_destination(&self.fertilizer_to_water, &fertilizer);
-        let light = self.find_destination(&self.water_to_light, &water);
-        let temperature = self.find_destination(&self.light_to_temperature, &light);
-        let humidity = self.find_destination(&self.temperature_to_humidity, &temperature);
-        let location = self.find_destination(&self.humidity_to_location, &humidity);
+    pub fn find_location_for_seed(&self, seed: &usize) -> usize {
+        let soil = self.find_destination("soil", &self.seed_to_soil, seed);
+        let fertilizer = self.find_destination("fertilizer", &self.soil_to_fertilizer, &soil);
+        let water = self.find_destination("water", &self.fertilizer_to_water, &fertilizer);
+        let light = self.find_destination("light", &self.water_to_light, &water);
+        let temperature = self.find_destination("temperature", &self.light_to_temperature, &light);
+        let humidity = self.find_destination("humidity", &self.temperature_to_humidity, &temperature);
+        let location = self.find_destination("location", &self.humidity_to_location, &humidity);
         return location;
     }
 
-    fn find_destination(&self, mapping: &Vec<AlmanacMapping>, source: &usize) -> usize {
-        mapping.iter().find_map(|map| map.get(source)).unwrap_or(*source)
+    fn categories(&self) -> [&[AlmanacMapping]; 7] {
+        [&self.seed_to_soil, &self.soil_to_fertilizer, &self.fertilizer_to_water, &self.water_to_light,
+            &self.light_to_temperature, &self.temperature_to_humidity, &self.humidity_to_location]
     }
-}
 
-pub fn aoc_5_1() -> usize {
-    return parse_and_find_nearest_location("res/aoc5.txt");
+    fn find_destination(&self, category: &str, mapping: &[AlmanacMapping], source: &usize) -> usize {
+        let destination = mapping.iter().find_map(|map| map.get(source)).unwrap_or(*source);
+        trace_event!(5, "hop", category = category, source = source, destination = destination);
+        return destination;
+    }
 }
 
-pub fn aoc_5_2() -> usize {
-    return parse_with_pairs_and_find_nearest_location("res/aoc5.txt");
+pub struct Aoc5;
+
+static ALTERNATIVES: &[Alternative<Almanac>] = &[
+    Alternative { part: 2, name: "ranges", solve: find_nearest_location_of_seed_ranges },
+];
+
+impl Solution for Aoc5 {
+    type Input = Almanac;
+
+    fn day(&self) -> usize {
+        5
+    }
+
+    fn parse(&self, input_data: &str) -> Almanac {
+        parse_input_data(input_data)
+    }
+
+    fn part_1(&self, almanac: &Almanac) -> usize {
+        find_nearest_location(almanac)
+    }
+
+    fn part_2(&self, almanac: &Almanac) -> usize {
+        find_nearest_location_with_seed_pairs(almanac)
+    }
+
+    fn alternatives(&self) -> &'static [Alternative<Almanac>] {
+        ALTERNATIVES
+    }
+
+    fn queries(&self) -> &'static [&'static str] {
+        &["location SEED"]
+    }
+
+    fn query(&self, almanac: &Almanac, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["location", seed] => Ok(almanac.map.find_location_for_seed(&parse_argument(seed)?).to_string()),
+            _ => Err(format!("unknown query {}", query.join(" "))),
+        }
+    }
 }
 
-fn parse_and_find_nearest_location(input_file: &str) -> usize {
-    let input_data = read_or_panic(input_file);
-    let almanac = parse_input_data(&input_data);
+pub fn find_nearest_location(almanac: &Almanac) -> usize {
     return almanac.seeds.iter().map(|seed| almanac.map.find_location_for_seed(seed)).min().unwrap();
 }
 
-fn parse_with_pairs_and_find_nearest_location(input_file: &str) -> usize {
-    let input_data = read_or_panic(input_file);
-    let almanac = parse_input_data_with_seed_pairs(&input_data);
-    return almanac.seeds.iter().map(|seed_range| {
+pub fn find_nearest_location_with_seed_pairs(almanac: &Almanac) -> usize {
+    return create_seed_ranges_from_pairs(&almanac.seeds).iter().map(|seed_range| {
         let mut min = almanac.map.find_location_for_seed(&seed_range.start);
         for seed in (seed_range.start+1)..(seed_range.start + seed_range.length) {
             let location = almanac.map.find_location_for_seed(&seed);
@@ -91,7 +129,45 @@ fn parse_with_pairs_and_find_nearest_location(input_file: &str) -> usize {
         return min;
     }).min().unwrap();
 }
-const ALMANAC_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(
+
+/// Maps whole seed ranges from category to category, splitting them where the mappings start and end,
+/// instead of following every single seed.
+pub fn find_nearest_location_of_seed_ranges(almanac: &Almanac) -> usize {
+    let mut ranges: Vec<Range<usize>> = create_seed_ranges_from_pairs(&almanac.seeds).iter()
+        .map(|seed_range| seed_range.start..(seed_range.start + seed_range.length))
+        .filter(|range| !range.is_empty())
+        .collect();
+    for mapping in almanac.map.categories() {
+        ranges = ranges.into_iter().flat_map(|range| map_range(mapping, range)).collect();
+    }
+    return ranges.iter().map(|range| range.start).min().unwrap();
+}
+
+fn map_range(mapping: &[AlmanacMapping], range: Range<usize>) -> Vec<Range<usize>> {
+    let mut mapped = Vec::new();
+    let mut unmapped = vec![range];
+    for map in mapping {
+        let mut remaining = Vec::new();
+        for range in unmapped {
+            let overlap = range.start.max(map.source)..range.end.min(map.source + map.length);
+            if overlap.is_empty() {
+                remaining.push(range);
+                continue;
+            }
+            mapped.push((map.destination + overlap.start - map.source)..(map.destination + overlap.end - map.source));
+            if range.start < overlap.start {
+                remaining.push(range.start..overlap.start);
+            }
+            if overlap.end < range.end {
+                remaining.push(overlap.end..range.end);
+            }
+        }
+        unmapped = remaining;
+    }
+    mapped.extend(unmapped);
+    return mapped;
+}
+static ALMANAC_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(
 r"^seeds:(?<seeds>(\s*\d+)+)\n
 seed-to-soil map:
 (?<seed_to_soil>((\d+\s*)*\n*)*)
@@ -108,7 +184,7 @@ temperature-to-humidity map:
 humidity-to-location map:
 (?<humidity_to_location>((\d+\s*)*\n*)*)").unwrap());
 
-fn parse_input_data(input_data: &str) -> Almanac {
+pub fn parse_input_data(input_data: &str) -> Almanac {
     return ALMANAC_REGEX.captures(input_data).map(|matched_almanac| {
         Almanac {
             seeds: parse_numbers(&matched_almanac, "seeds"),
@@ -125,24 +201,7 @@ fn parse_input_data(input_data: &str) -> Almanac {
     }).unwrap();
 }
 
-fn parse_input_data_with_seed_pairs(input_data: &str) -> AlmanacWithSeedRange {
-    return ALMANAC_REGEX.captures(input_data).map(|matched_almanac| {
-        AlmanacWithSeedRange {
-            seeds: create_seed_ranges_from_pairs(&parse_numbers(&matched_almanac, "seeds")),
-            map: AlmanacMap {
-                seed_to_soil: parse_map(&matched_almanac, "seed_to_soil"),
-                soil_to_fertilizer: parse_map(&matched_almanac, "soil_to_fertilizer"),
-                fertilizer_to_water: parse_map(&matched_almanac, "fertilizer_to_water"),
-                water_to_light: parse_map(&matched_almanac, "water_to_light"),
-                light_to_temperature: parse_map(&matched_almanac, "light_to_temperature"),
-                temperature_to_humidity: parse_map(&matched_almanac, "temperature_to_humidity"),
-                humidity_to_location: parse_map(&matched_almanac, "humidity_to_location"),
-            },
-        }
-    }).unwrap();
-}
-
-fn create_seed_ranges_from_pairs(pairs: &Vec<usize>) -> Vec<SeedRange> {
+pub fn create_seed_ranges_from_pairs(pairs: &[usize]) -> Vec<SeedRange> {
     let mut seeds = Vec::new();
     for i in (0..pairs.len()).step_by(2) {
         let initial_seed = pairs[i];
@@ -158,7 +217,7 @@ fn create_seed_ranges_from_pairs(pairs: &Vec<usize>) -> Vec<SeedRange> {
 fn parse_map(capture: &Captures, group_name: &str) -> Vec<AlmanacMapping>{
     capture.name(group_name).unwrap().as_str().split("\n").fold(Vec::new(), |mut accu, line| {
         let mapping: Vec<usize> = line.split(" ").filter(|num| !num.is_empty()).map(|num| num.parse().unwrap()).collect();
-        if mapping.len() > 0 {
+        if !mapping.is_empty() {
             accu.push(AlmanacMapping {
                 source: mapping[1],
                 destination: mapping[0],
@@ -171,15 +230,19 @@ fn parse_map(capture: &Captures, group_name: &str) -> Vec<AlmanacMapping>{
 
 #[cfg(test)]
 mod tests {
-    use crate::aoc5::{parse_and_find_nearest_location, parse_with_pairs_and_find_nearest_location};
+    use crate::aoc5::{find_nearest_location, find_nearest_location_of_seed_ranges, find_nearest_location_with_seed_pairs, parse_input_data};
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_5_1() {
-        assert_eq!(parse_and_find_nearest_location( "res/aoc5-example.txt"), 35)
+        let almanac = parse_input_data(&example_input(5, 1));
+        assert_eq!(find_nearest_location(&almanac), example_answer(5, 1))
     }
 
     #[test]
     fn aoc_5_2() {
-        assert_eq!(parse_with_pairs_and_find_nearest_location( "res/aoc5-example.txt"), 46)
+        let almanac = parse_input_data(&example_input(5, 2));
+        assert_eq!(find_nearest_location_with_seed_pairs(&almanac), example_answer(5, 2));
+        assert_eq!(find_nearest_location_of_seed_ranges(&almanac), example_answer(5, 2))
     }
 }
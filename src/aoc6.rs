@@ -2,43 +2,84 @@ use std::ops::Range;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 
-use crate::fs_util::read_or_panic;
 use crate::parse_util::parse_numbers;
+use crate::solution::{Alternative, Solution};
+use crate::trace::trace_event;
 
-struct Race {
-    time: usize,
-    record_distance: usize
+#[derive(Serialize)]
+pub struct Race {
+    pub time: usize,
+    pub record_distance: usize
 }
 
-pub fn aoc_6_1() -> usize {
-    return parse_and_multiply_winning_options("res/aoc6.txt");
+pub struct Aoc6;
+
+static ALTERNATIVES: &[Alternative<Vec<Race>>] = &[
+    Alternative { part: 1, name: "closed-form", solve: |races| multiply_winning_ranges(races, find_winning_range_closed_form) },
+    Alternative { part: 2, name: "closed-form", solve: |races| find_winning_range_closed_form(&combine_races(races)).len() },
+];
+
+impl Solution for Aoc6 {
+    type Input = Vec<Race>;
+
+    fn day(&self) -> usize {
+        6
+    }
+
+    fn parse(&self, input_data: &str) -> Vec<Race> {
+        parse_input_data(input_data)
+    }
+
+    fn part_1(&self, races: &Vec<Race>) -> usize {
+        multiply_winning_options(races)
+    }
+
+    fn part_2(&self, races: &Vec<Race>) -> usize {
+        count_winning_options_of_single_race(races)
+    }
+
+    fn alternatives(&self) -> &'static [Alternative<Vec<Race>>] {
+        ALTERNATIVES
+    }
 }
 
-pub fn aoc_6_2() -> usize {
-    return parse_and_count_winning_options("res/aoc6.txt");
+pub fn multiply_winning_options(races: &[Race]) -> usize {
+    multiply_winning_ranges(races, find_winning_range)
 }
 
-fn parse_and_multiply_winning_options(input_file: &str) -> usize {
-    let input_data = read_or_panic(input_file);
-    return parse_input_data(&input_data).iter()
-        .map(|race| find_winning_range(race).len())
+fn multiply_winning_ranges(races: &[Race], find_range: fn(&Race) -> Range<usize>) -> usize {
+    return races.iter()
+        .map(|race| find_range(race).len())
         .reduce(|left, right| left * right)
         .unwrap_or(0);
 }
 
-fn parse_and_count_winning_options(input_file: &str) -> usize {
-    let race = parse_input_data_with_single_race(&read_or_panic(input_file));
+pub fn count_winning_options_of_single_race(races: &[Race]) -> usize {
+    let race = combine_races(races);
     return find_winning_range(&race).len();
 }
 
-fn find_winning_range(race: &Race) -> Range<usize> {
+pub fn combine_races(races: &[Race]) -> Race {
+    Race {
+        time: concat_numbers(races.iter().map(|race| race.time)),
+        record_distance: concat_numbers(races.iter().map(|race| race.record_distance)),
+    }
+}
+
+fn concat_numbers(numbers: impl Iterator<Item=usize>) -> usize {
+    numbers.map(|number| number.to_string()).collect::<String>().parse().unwrap()
+}
+
+pub fn find_winning_range(race: &Race) -> Range<usize> {
     let mut lower_bound = 0;
     let mut min_button_press = race.time + 1;
     let mut button_press_candidate = race.time / 4;
     while button_press_candidate != min_button_press {
         let time_left = race.time - button_press_candidate;
         let distance = button_press_candidate * time_left;
+        trace_event!(6, "probe", button_press = button_press_candidate, distance = distance, wins = distance > race.record_distance);
         if distance > race.record_distance {
             min_button_press = button_press_candidate;
             button_press_candidate -= half(&lower_bound, &button_press_candidate);
@@ -50,15 +91,31 @@ fn find_winning_range(race: &Race) -> Range<usize> {
     return min_button_press..(race.time - min_button_press + 1);
 }
 
+/// Solves `button_press * (time - button_press) > record_distance` for the smallest button press, and
+/// corrects the rounding errors of the floating point root.
+pub fn find_winning_range_closed_form(race: &Race) -> Range<usize> {
+    let wins = |button_press: usize| button_press * (race.time - button_press) > race.record_distance;
+    let time = race.time as f64;
+    let root = (time - (time * time - 4.0 * race.record_distance as f64).max(0.0).sqrt()) / 2.0;
+    let mut min_button_press = (root.floor() as usize).min(race.time / 2);
+    while min_button_press > 0 && wins(min_button_press - 1) {
+        min_button_press -= 1;
+    }
+    while min_button_press <= race.time / 2 && !wins(min_button_press) {
+        min_button_press += 1;
+    }
+    return min_button_press..(race.time - min_button_press + 1);
+}
+
 fn half(from: &usize, to: &usize) -> usize {
     ((to - from) as f32 / 2.0).ceil() as usize
 }
 
-const RACES_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(
+static RACES_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(
 r"^Time:(?<times>(\s*\d+)+)
 Distance:(?<distances>(\s*\d+)+)").unwrap());
 
-fn parse_input_data(input_data: &str) -> Vec<Race> {
+pub fn parse_input_data(input_data: &str) -> Vec<Race> {
     return RACES_REGEX.captures(input_data).map(|matched_races| {
         let times = parse_numbers(&matched_races, "times");
         let distances = parse_numbers(&matched_races, "distances");
@@ -71,26 +128,30 @@ fn parse_input_data(input_data: &str) -> Vec<Race> {
     }).unwrap();
 }
 
-fn parse_input_data_with_single_race(input_data: &str) -> Race {
-    return RACES_REGEX.captures(input_data).map(|matched_races|
-        Race {
-            time: matched_races.name("times").unwrap().as_str().replace(" ", "").parse().unwrap(),
-            record_distance: matched_races.name("distances").unwrap().as_str().replace(" ", "").parse().unwrap(),
-        }
-    ).unwrap();
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::aoc6::{parse_and_count_winning_options, parse_and_multiply_winning_options};
+    use crate::aoc6::{count_winning_options_of_single_race, find_winning_range_closed_form, multiply_winning_options, parse_input_data, Race};
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_6_1() {
-        assert_eq!(parse_and_multiply_winning_options( "res/aoc6-example.txt"), 288)
+        let races = parse_input_data(&example_input(6, 1));
+        assert_eq!(multiply_winning_options(&races), example_answer(6, 1))
     }
 
     #[test]
     fn aoc_6_2() {
-        assert_eq!(parse_and_count_winning_options( "res/aoc6-example.txt"), 71503)
+        let races = parse_input_data(&example_input(6, 2));
+        assert_eq!(count_winning_options_of_single_race(&races), example_answer(6, 2))
+    }
+
+    #[test]
+    fn closed_form_counts_all_winning_button_presses() {
+        for time in 1..60 {
+            for record_distance in 0..(time * time / 4 + 2) {
+                let wins = (0..=time).filter(|button_press| button_press * (time - button_press) > record_distance).count();
+                assert_eq!(find_winning_range_closed_form(&Race { time, record_distance }).len(), wins, "time {time}, record {record_distance}");
+            }
+        }
     }
 }
@@ -3,13 +3,14 @@ use std::collections::HashMap;
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 use crate::aoc7::Deck::{ClassicDeck, JokerDeck};
 use crate::aoc7::HandType::{FiveOfAKind, FourOfAKind, FullHouse, HighCard, OnePair, ThreeOfAKind, TwoPair};
 
-use crate::fs_util::read_or_panic;
+use crate::solution::Solution;
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash, Debug)]
-enum ClassicCard {
+#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash, Debug, Serialize)]
+pub enum ClassicCard {
     Two,
     Three,
     Four,
@@ -25,8 +26,8 @@ enum ClassicCard {
     Ass,
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash, Debug)]
-enum JokerCard {
+#[derive(Ord, PartialOrd, Eq, PartialEq, Copy, Clone, Hash, Debug, Serialize)]
+pub enum JokerCard {
     Joker,
     Two,
     Three,
@@ -43,7 +44,7 @@ enum JokerCard {
 }
 
 #[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
-enum HandType {
+pub enum HandType {
     HighCard,
     OnePair,
     TwoPair,
@@ -53,20 +54,20 @@ enum HandType {
     FiveOfAKind,
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq)]
-enum Deck {
+#[derive(Ord, PartialOrd, Eq, PartialEq, Clone, Serialize)]
+pub enum Deck {
     ClassicDeck([ClassicCard; 5]),
     JokerDeck([JokerCard; 5]),
 }
 
-#[derive(Ord, Eq, PartialEq)]
-struct Hand {
-    hand: Deck,
-    bid: usize
+#[derive(Eq, PartialEq, Clone, Serialize)]
+pub struct Hand {
+    pub hand: Deck,
+    pub bid: usize
 }
 
 impl Hand {
-    fn get_hand_type(&self) -> HandType {
+    pub fn get_hand_type(&self) -> HandType {
         match self.hand {
             ClassicDeck(hand) =>
                 get_classic_hand_type(&hand),
@@ -74,6 +75,36 @@ impl Hand {
                 get_joker_hand_type(&hand)
         }
     }
+
+    pub fn with_jokers(&self) -> Hand {
+        match self.hand {
+            ClassicDeck(hand) => Hand {
+                hand: JokerDeck(hand.map(JokerCard::from)),
+                bid: self.bid,
+            },
+            JokerDeck(_) => self.clone(),
+        }
+    }
+}
+
+impl From<ClassicCard> for JokerCard {
+    fn from(card: ClassicCard) -> Self {
+        match card {
+            ClassicCard::Two => JokerCard::Two,
+            ClassicCard::Three => JokerCard::Three,
+            ClassicCard::Four => JokerCard::Four,
+            ClassicCard::Five => JokerCard::Five,
+            ClassicCard::Six => JokerCard::Six,
+            ClassicCard::Seven => JokerCard::Seven,
+            ClassicCard::Eight => JokerCard::Eight,
+            ClassicCard::Nine => JokerCard::Nine,
+            ClassicCard::Ten => JokerCard::Ten,
+            ClassicCard::Joker => JokerCard::Joker,
+            ClassicCard::Queen => JokerCard::Queen,
+            ClassicCard::King => JokerCard::King,
+            ClassicCard::Ass => JokerCard::Ass,
+        }
+    }
 }
 
 fn get_classic_hand_type(hand: &[ClassicCard; 5]) -> HandType {
@@ -127,39 +158,83 @@ fn get_hand_type_by_counts(highest_count: &usize, second_highest_count: &usize)
     }
 }
 
-impl PartialOrd<Self> for Hand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> Ordering {
         match self.get_hand_type().cmp(&other.get_hand_type()) {
             Ordering::Equal =>
-                Some(self.hand.cmp(&other.hand)),
-            ordering => Some(ordering),
+                self.hand.cmp(&other.hand),
+            ordering => ordering,
         }
     }
 }
 
-pub fn aoc_7_1() -> usize {
-    return parse_and_calculate_total_winnings("res/aoc7.txt");
+impl PartialOrd<Self> for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-pub fn aoc_7_2() -> usize {
-    return parse_and_calculate_total_winnings_with_joker("res/aoc7.txt");
+pub struct Aoc7;
+
+impl Solution for Aoc7 {
+    type Input = Vec<Hand>;
+
+    fn day(&self) -> usize {
+        7
+    }
+
+    fn parse(&self, input_data: &str) -> Vec<Hand> {
+        parse_input_data(input_data)
+    }
+
+    fn part_1(&self, hands: &Vec<Hand>) -> usize {
+        calculate_total_winnings(hands)
+    }
+
+    fn part_2(&self, hands: &Vec<Hand>) -> usize {
+        calculate_total_winnings_with_joker(hands)
+    }
+
+    fn queries(&self) -> &'static [&'static str] {
+        &["rank CARDS"]
+    }
+
+    fn query(&self, hands: &Vec<Hand>, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["rank", cards] => {
+                let Some(cards) = parse_cards(cards) else {
+                    return Err(format!("{cards} are not five cards"));
+                };
+                let hand = Hand {
+                    hand: ClassicDeck(cards),
+                    bid: 0,
+                };
+                let joker_hand = hand.with_jokers();
+                let rank = hands.iter().filter(|other| **other < hand).count() + 1;
+                let joker_rank = hands.iter().filter(|other| other.with_jokers() < joker_hand).count() + 1;
+                Ok(format!("{:?} at rank {rank}, with jokers {:?} at rank {joker_rank} of {} hands",
+                           hand.get_hand_type(), joker_hand.get_hand_type(), hands.len()))
+            }
+            _ => Err(format!("unknown query {}", query.join(" "))),
+        }
+    }
 }
 
-fn parse_and_calculate_total_winnings(input_file: &str) -> usize {
-    let mut hands = parse_classic_input_data(&read_or_panic(input_file));
+pub fn calculate_total_winnings(hands: &[Hand]) -> usize {
+    let mut hands = hands.to_vec();
     hands.sort();
     return hands.iter().enumerate().map(|(index, hand)| (index + 1) * hand.bid).sum();
 }
 
-fn parse_and_calculate_total_winnings_with_joker(input_file: &str) -> usize {
-    let mut hands = parse_joker_input_data(&read_or_panic(input_file));
+pub fn calculate_total_winnings_with_joker(hands: &[Hand]) -> usize {
+    let mut hands: Vec<Hand> = hands.iter().map(|hand| hand.with_jokers()).collect();
     hands.sort();
     return hands.iter().enumerate().map(|(index, hand)| (index + 1) * hand.bid).sum();
 }
 
-const HAND_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<hand>\w{5})\s+(?<bid>\d+)").unwrap());
+static HAND_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<hand>\w{5})\s+(?<bid>\d+)").unwrap());
 
-const CLASSIC_HAND_MAPPING: Lazy<HashMap<char, ClassicCard>> = Lazy::new(|| HashMap::from([
+static CLASSIC_HAND_MAPPING: Lazy<HashMap<char, ClassicCard>> = Lazy::new(|| HashMap::from([
     ('2', ClassicCard::Two),
     ('3', ClassicCard::Three),
     ('4', ClassicCard::Four),
@@ -175,23 +250,12 @@ const CLASSIC_HAND_MAPPING: Lazy<HashMap<char, ClassicCard>> = Lazy::new(|| Hash
     ('A', ClassicCard::Ass),
 ]));
 
-const JOKER_HAND_MAPPING: Lazy<HashMap<char, JokerCard>> = Lazy::new(|| HashMap::from([
-    ('2', JokerCard::Two),
-    ('3', JokerCard::Three),
-    ('4', JokerCard::Four),
-    ('5', JokerCard::Five),
-    ('6', JokerCard::Six),
-    ('7', JokerCard::Seven),
-    ('8', JokerCard::Eight),
-    ('9', JokerCard::Nine),
-    ('T', JokerCard::Ten),
-    ('J', JokerCard::Joker),
-    ('K', JokerCard::King),
-    ('Q', JokerCard::Queen),
-    ('A', JokerCard::Ass),
-]));
+fn parse_cards(cards: &str) -> Option<[ClassicCard; 5]> {
+    let cards: Vec<ClassicCard> = cards.chars().map(|char| CLASSIC_HAND_MAPPING.get(&char).copied()).collect::<Option<Vec<ClassicCard>>>()?;
+    return cards.try_into().ok();
+}
 
-fn parse_classic_input_data(input_data: &str) -> Vec<Hand> {
+pub fn parse_input_data(input_data: &str) -> Vec<Hand> {
     return HAND_REGEX.captures_iter(input_data).map(|matched_hand| {
         let hand: Vec<ClassicCard> = matched_hand.name("hand").unwrap().as_str().chars().map(|char|
             CLASSIC_HAND_MAPPING[&char]
@@ -203,30 +267,21 @@ fn parse_classic_input_data(input_data: &str) -> Vec<Hand> {
     }).collect();
 }
 
-fn parse_joker_input_data(input_data: &str) -> Vec<Hand> {
-    return HAND_REGEX.captures_iter(input_data).map(|matched_hand| {
-        let hand: Vec<JokerCard> = matched_hand.name("hand").unwrap().as_str().chars().map(|char|
-            JOKER_HAND_MAPPING[&char]
-        ).collect();
-        Hand {
-            hand: JokerDeck(hand.try_into().unwrap()),
-            bid: matched_hand.name("bid").unwrap().as_str().parse().unwrap()
-        }
-    }).collect();
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::aoc7::{parse_and_calculate_total_winnings, parse_and_calculate_total_winnings_with_joker};
+    use crate::aoc7::{calculate_total_winnings, calculate_total_winnings_with_joker, parse_input_data};
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_7_1() {
-        assert_eq!(parse_and_calculate_total_winnings( "res/aoc7-example.txt"), 6440)
+        let hands = parse_input_data(&example_input(7, 1));
+        assert_eq!(calculate_total_winnings(&hands), example_answer(7, 1))
     }
 
     #[test]
     fn aoc_7_2() {
-        assert_eq!(parse_and_calculate_total_winnings_with_joker( "res/aoc7-example.txt"), 5905)
+        let hands = parse_input_data(&example_input(7, 2));
+        assert_eq!(calculate_total_winnings_with_joker(&hands), example_answer(7, 2))
     }
 
 }
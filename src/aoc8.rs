@@ -2,54 +2,59 @@ use std::collections::{HashMap, HashSet};
 
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 
 use crate::aoc8::Direction::{Left, Right};
-use crate::fs_util::read_or_panic;
+use crate::solution::Solution;
+use crate::trace::trace_event;
 
-enum Direction {
+#[derive(Serialize)]
+pub enum Direction {
     Left,
     Right,
 }
 
-#[derive(Clone, Eq, PartialEq, Hash)]
-struct Vertex {
-    name: String,
-    left: String,
-    right: String,
+#[derive(Clone, Eq, PartialEq, Hash, Serialize)]
+pub struct Vertex {
+    pub name: String,
+    pub left: String,
+    pub right: String,
 }
 
-struct Map {
-    directions: Vec<Direction>,
-    vertices: HashMap<String, Vertex>
+#[derive(Serialize)]
+pub struct Map {
+    pub directions: Vec<Direction>,
+    pub vertices: HashMap<String, Vertex>
 }
 
 impl Map {
-    fn follow_directions(&self, from: &str, to: &str) -> Vec<Vertex> {
+    /// Walks the directions from `from` until `to`, repeating them as often as needed. Fails if a vertex is unknown,
+    /// or if the walk comes back to a vertex at the same position in the directions without having passed `to`.
+    pub fn follow_directions(&self, from: &str, to: &str) -> Result<Vec<Vertex>, String> {
         let Some(mut current_vertex) = self.vertices.get(from) else {
-            panic!("Cannot find {from}");
+            return Err(format!("Cannot find {from}"));
         };
-        let mut path = Vec::new();
-        path.push(current_vertex.clone());
-        for direction in &self.directions {
+        let mut path = vec![current_vertex.clone()];
+        let mut visited = HashSet::new();
+        for (index, direction) in self.directions.iter().enumerate().cycle() {
             if current_vertex.name == to {
-                return path;
+                return Ok(path);
+            }
+            if !visited.insert((&current_vertex.name, index)) {
+                return Err(format!("{to} is unreachable from {from}"));
             }
             let next = match direction {
                 Left => &current_vertex.left,
                 Right => &current_vertex.right,
             };
-            current_vertex = self.vertices.get(next).expect(&format!("Cannot find {next}"));
+            trace_event!(8, "step", from = current_vertex.name, direction = if matches!(direction, Left) { "L" } else { "R" }, to = next);
+            current_vertex = self.vertices.get(next).ok_or_else(|| format!("Cannot find {next}"))?;
             path.push(current_vertex.clone());
         }
-        if current_vertex.name != to {
-            let mut next_path = self.follow_directions(&current_vertex.name, to);
-            next_path.remove(0);
-            path.append(&mut next_path);
-        }
-        return path;
+        unreachable!("the directions are never empty")
     }
 
-    fn ghost_directions_length(&self, from: &str, to: &str) -> usize {
+    pub fn ghost_directions_length(&self, from: &str, to: &str) -> usize {
         let mut start: HashSet<Vertex> = self.vertices.iter()
             .filter(|(name, _)| name.ends_with(from))
             .map(|(_, vertex)| vertex.clone())
@@ -76,36 +81,68 @@ impl Map {
                 Left => current_vertices.iter().map(|v| v.left.clone()).collect(),
                 Right => current_vertices.iter().map(|v| v.right.clone()).collect(),
             };
-            current_vertices = next.iter().map(|v |self.vertices.get(v).expect(&format!("Cannot find {v}")).clone()).collect();
+            current_vertices = next.iter().map(|v |self.vertices.get(v).unwrap_or_else(|| panic!("Cannot find {v}")).clone()).collect();
             path.push(current_vertices.clone());
         }
         return path;
     }
 }
 
-pub fn aoc_8_1() -> usize {
-    return parse_and_calculate_path_length("res/aoc8.txt");
-}
+pub struct Aoc8;
+
+impl Solution for Aoc8 {
+    type Input = Map;
+
+    fn day(&self) -> usize {
+        8
+    }
+
+    fn parse(&self, input_data: &str) -> Map {
+        parse_input_data(input_data)
+    }
 
-pub fn aoc_8_2() -> usize {
-    return parse_and_calculate_ghost_path_length("res/aoc8.txt");
+    fn part_1(&self, map: &Map) -> usize {
+        calculate_path_length(map)
+    }
+
+    fn part_2(&self, map: &Map) -> usize {
+        calculate_ghost_path_length(map)
+    }
+
+    fn queries(&self) -> &'static [&'static str] {
+        &["path FROM TO"]
+    }
+
+    fn query(&self, map: &Map, query: &[&str]) -> Result<String, String> {
+        match query {
+            ["path", from, to] => {
+                let vertices = map.follow_directions(from, to)?;
+                let path: Vec<&str> = vertices.iter().map(|vertex| vertex.name.as_str()).collect();
+                let shown = if path.len() > 10 {
+                    format!("{} -> ... -> {}", path[..5].join(" -> "), path[path.len() - 5..].join(" -> "))
+                } else {
+                    path.join(" -> ")
+                };
+                Ok(format!("{} steps: {shown}", path.len() - 1))
+            }
+            _ => Err(format!("unknown query {}", query.join(" "))),
+        }
+    }
 }
 
-fn parse_and_calculate_path_length(input_file: &str) -> usize {
-    let map = parse_input_data(&read_or_panic(input_file));
-    return map.follow_directions("AAA", "ZZZ").len() - 1;
+pub fn calculate_path_length(map: &Map) -> usize {
+    return map.follow_directions("AAA", "ZZZ").unwrap_or_else(|message| panic!("{message}")).len() - 1;
 }
 
-fn parse_and_calculate_ghost_path_length(input_file: &str) -> usize {
-    let map = parse_input_data(&read_or_panic(input_file));
+pub fn calculate_ghost_path_length(map: &Map) -> usize {
     return map.ghost_directions_length("A", "Z");
 }
 
-const MAP_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?<directions>[LR]+)\n\n(?<vertices>(.*(\n|$))*)").unwrap());
+static MAP_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(?<directions>[LR]+)\n\n(?<vertices>(.*(\n|$))*)").unwrap());
 
-const VERTEX_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<name>\w+)\s=\s\((?<left>\w+),\s(?<right>\w+)\)").unwrap());
+static VERTEX_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<name>\w+)\s=\s\((?<left>\w+),\s(?<right>\w+)\)").unwrap());
 
-fn parse_input_data(input_data: &str) -> Map {
+pub fn parse_input_data(input_data: &str) -> Map {
     return MAP_REGEX.captures(input_data).map(|matched_map| {
         let vertices_as_str: HashMap<&str, (&str, &str)> = VERTEX_REGEX.captures_iter(matched_map.name("vertices").unwrap().as_str()).map(|matched_vertex|
             (matched_vertex.name("name").unwrap().as_str(), (matched_vertex.name("left").unwrap().as_str(), matched_vertex.name("right").unwrap().as_str()))
@@ -133,16 +170,27 @@ fn parse_input_data(input_data: &str) -> Map {
 
 #[cfg(test)]
 mod tests {
-    use crate::aoc8::{parse_and_calculate_ghost_path_length, parse_and_calculate_path_length};
+    use crate::aoc8::{calculate_ghost_path_length, calculate_path_length, parse_input_data};
+    use crate::test_util::{example_answer, example_input};
 
     #[test]
     fn aoc_8_1() {
-        assert_eq!(parse_and_calculate_path_length( "res/aoc8-1-example.txt"), 6)
+        let map = parse_input_data(&example_input(8, 1));
+        assert_eq!(calculate_path_length(&map), example_answer(8, 1))
     }
 
     #[test]
     fn aoc_8_2() {
-        assert_eq!(parse_and_calculate_ghost_path_length( "res/aoc8-2-example.txt"), 6)
+        let map = parse_input_data(&example_input(8, 2));
+        assert_eq!(calculate_ghost_path_length(&map), example_answer(8, 2))
+    }
+
+    #[test]
+    fn target_off_the_walk_is_unreachable() {
+        let map = parse_input_data("LL\n\nAAA = (BBB, CCC)\nBBB = (BBB, BBB)\nCCC = (CCC, CCC)\n");
+        assert_eq!(map.follow_directions("AAA", "CCC").err(), Some("CCC is unreachable from AAA".to_string()));
+        assert_eq!(map.follow_directions("AAA", "DDD").err(), Some("DDD is unreachable from AAA".to_string()));
+        assert_eq!(map.follow_directions("DDD", "AAA").err(), Some("Cannot find DDD".to_string()))
     }
 
 }
@@ -0,0 +1,183 @@
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+
+use crate::output::input_hash;
+use crate::runner::Task;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Phase {
+    Parse,
+    Part(usize),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part {part}"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "cannot build statistics without samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let middle = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[middle - 1] + sorted[middle]) / 2
+        } else {
+            sorted[middle]
+        };
+        let p95_rank = (sorted.len() * 95).div_ceil(100);
+        Stats {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+            p95: sorted[p95_rank - 1],
+        }
+    }
+
+    fn add(&self, other: &Stats) -> Stats {
+        Stats {
+            min: self.min + other.min,
+            median: self.median + other.median,
+            mean: self.mean + other.mean,
+            p95: self.p95 + other.p95,
+        }
+    }
+}
+
+pub struct Benchmark {
+    pub day: usize,
+    pub phase: Phase,
+    pub stats: Stats,
+    pub input_sha256: String,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum BenchFormat {
+    Table,
+    Csv,
+}
+
+pub fn bench_tasks(tasks: &[Task], iterations: usize) -> Vec<Benchmark> {
+    let mut benchmarks = Vec::new();
+    for task in tasks {
+        let input_data = task.read();
+        let input_sha256 = input_hash(&input_data);
+        let (input, parse_samples) = measure(iterations, || task.parse(&input_data));
+        benchmarks.push(Benchmark {
+            day: task.day.day(),
+            phase: Phase::Parse,
+            stats: Stats::from_samples(&parse_samples),
+            input_sha256: input_sha256.clone(),
+        });
+        for part in &task.parts {
+            let (_, solve_samples) = measure(iterations, || task.solve(*part, input.as_ref()));
+            benchmarks.push(Benchmark {
+                day: task.day.day(),
+                phase: Phase::Part(*part),
+                stats: Stats::from_samples(&solve_samples),
+                input_sha256: input_sha256.clone(),
+            });
+        }
+    }
+    return benchmarks;
+}
+
+pub(crate) fn measure<T>(iterations: usize, mut run: impl FnMut() -> T) -> (T, Vec<Duration>) {
+    let mut samples = Vec::with_capacity(iterations);
+    let mut result = None;
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let value = run();
+        samples.push(start.elapsed());
+        result = Some(value);
+    }
+    return (result.unwrap(), samples);
+}
+
+pub fn total(benchmarks: &[Benchmark]) -> Option<Stats> {
+    benchmarks.iter().map(|benchmark| benchmark.stats).reduce(|sum, stats| sum.add(&stats))
+}
+
+pub fn print_benchmarks(benchmarks: &[Benchmark], format: BenchFormat) {
+    match format {
+        BenchFormat::Table => print_table(benchmarks),
+        BenchFormat::Csv => print_csv(benchmarks),
+    }
+}
+
+fn print_table(benchmarks: &[Benchmark]) {
+    println!("{:>5}  {:<7}  {:>12}  {:>12}  {:>12}  {:>12}", "day", "phase", "min", "median", "mean", "p95");
+    for benchmark in benchmarks {
+        print_table_row(&benchmark.day.to_string(), &benchmark.phase.to_string(), &benchmark.stats);
+    }
+    if let Some(total) = total(benchmarks) {
+        print_table_row("total", "", &total);
+    }
+}
+
+fn print_table_row(day: &str, phase: &str, stats: &Stats) {
+    println!("{:>5}  {:<7}  {:>12}  {:>12}  {:>12}  {:>12}", day, phase,
+             format!("{:.2?}", stats.min), format!("{:.2?}", stats.median),
+             format!("{:.2?}", stats.mean), format!("{:.2?}", stats.p95));
+}
+
+fn print_csv(benchmarks: &[Benchmark]) {
+    println!("day,phase,min_ns,median_ns,mean_ns,p95_ns");
+    for benchmark in benchmarks {
+        print_csv_row(&benchmark.day.to_string(), &benchmark.phase.to_string(), &benchmark.stats);
+    }
+    if let Some(total) = total(benchmarks) {
+        print_csv_row("total", "", &total);
+    }
+}
+
+fn print_csv_row(day: &str, phase: &str, stats: &Stats) {
+    println!("{},{},{},{},{},{}", day, phase,
+             stats.min.as_nanos(), stats.median.as_nanos(), stats.mean.as_nanos(), stats.p95.as_nanos());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::bench::Stats;
+
+    fn millis(samples: &[u64]) -> Vec<Duration> {
+        samples.iter().map(|sample| Duration::from_millis(*sample)).collect()
+    }
+
+    #[test]
+    fn stats_of_odd_number_of_samples() {
+        assert_eq!(Stats::from_samples(&millis(&[5, 1, 3])), Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_millis(3),
+            mean: Duration::from_millis(3),
+            p95: Duration::from_millis(5),
+        })
+    }
+
+    #[test]
+    fn stats_of_even_number_of_samples() {
+        let samples = millis(&(1..=20).rev().collect::<Vec<u64>>());
+        assert_eq!(Stats::from_samples(&samples), Stats {
+            min: Duration::from_millis(1),
+            median: Duration::from_micros(10_500),
+            mean: Duration::from_micros(10_500),
+            p95: Duration::from_millis(19),
+        })
+    }
+}
@@ -0,0 +1,227 @@
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::bench::Benchmark;
+use crate::history::{append_entry, read_entries};
+
+/// Local, not checked in log of recorded benchmark runs, one JSON object per line.
+pub const BENCH_HISTORY_FILE: &str = ".aoc-bench-history.jsonl";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PhaseMedian {
+    pub day: usize,
+    pub phase: String,
+    pub median_ns: u64,
+    /// SHA-256 of the input the phase ran on, empty in runs recorded before it was tracked.
+    #[serde(default)]
+    pub input_sha256: String,
+}
+
+/// Median times of one benchmark run, recorded at a git commit.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BenchRun {
+    pub commit: String,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Whether the benchmarks ran in a debug build.
+    #[serde(default)]
+    pub debug: bool,
+    pub recorded_at: u64,
+    pub medians: Vec<PhaseMedian>,
+}
+
+impl BenchRun {
+    pub fn new(commit: String, dirty: bool, recorded_at: u64, benchmarks: &[Benchmark]) -> BenchRun {
+        let medians = benchmarks.iter().map(|benchmark| PhaseMedian {
+            day: benchmark.day,
+            phase: benchmark.phase.to_string(),
+            median_ns: benchmark.stats.median.as_nanos() as u64,
+            input_sha256: benchmark.input_sha256.clone(),
+        }).collect();
+        BenchRun {
+            commit,
+            dirty,
+            debug: cfg!(debug_assertions),
+            recorded_at,
+            medians,
+        }
+    }
+}
+
+pub struct BenchHistory {
+    pub runs: Vec<BenchRun>,
+}
+
+impl BenchHistory {
+    pub fn read(file: &Path) -> io::Result<BenchHistory> {
+        return Ok(BenchHistory {
+            runs: read_entries(file)?
+        });
+    }
+
+    pub fn append(&mut self, file: &Path, run: BenchRun) -> io::Result<()> {
+        append_entry(file, &run)?;
+        self.runs.push(run);
+        return Ok(());
+    }
+
+    /// Returns the latest run of the commit starting with `commit`, or without one the latest run of another
+    /// commit than `current_commit`. Only runs of the same build profile as the current one, debug or release, qualify.
+    pub fn baseline(&self, commit: Option<&str>, current_commit: &str, debug: bool) -> Option<&BenchRun> {
+        self.runs.iter().rev().filter(|run| run.debug == debug).find(|run| match commit {
+            Some(commit) => run.commit.starts_with(commit),
+            None => run.commit != current_commit,
+        })
+    }
+}
+
+/// Describes the benchmarks as a run at the commit checked out in `root`.
+pub fn current_run(root: &Path, benchmarks: &[Benchmark], now: u64) -> io::Result<BenchRun> {
+    let (commit, dirty) = current_commit(root)?;
+    return Ok(BenchRun::new(commit, dirty, now, benchmarks));
+}
+
+/// Returns the commit checked out in `root` and whether the working tree has uncommitted changes.
+pub fn current_commit(root: &Path) -> io::Result<(String, bool)> {
+    let commit = git(root, &["rev-parse", "HEAD"])?;
+    let changes = git(root, &["status", "--porcelain", "--untracked-files=no"])?;
+    return Ok((commit, !changes.is_empty()));
+}
+
+fn git(root: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git").arg("-C").arg(root).args(args).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+}
+
+/// Median time of one phase in the baseline and in the current run.
+#[derive(PartialEq, Eq, Debug)]
+pub struct PhaseChange {
+    pub day: usize,
+    pub phase: String,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl PhaseChange {
+    /// Change of the median relative to the baseline in percent, positive if it got slower.
+    pub fn percent(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64().max(f64::MIN_POSITIVE) - 1.0) * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.percent() > threshold_percent
+    }
+}
+
+/// Pairs the phases of the current run with the ones of the baseline on the same input, phases the baseline
+/// lacks or ran on another input are left out.
+pub fn compare_to_baseline(baseline: &BenchRun, current: &BenchRun) -> Vec<PhaseChange> {
+    current.medians.iter().filter_map(|median| {
+        let baseline_median = baseline.medians.iter().find(|baseline_median|
+            baseline_median.day == median.day && baseline_median.phase == median.phase && baseline_median.input_sha256 == median.input_sha256
+        )?;
+        Some(PhaseChange {
+            day: median.day,
+            phase: median.phase.clone(),
+            baseline: Duration::from_nanos(baseline_median.median_ns),
+            current: Duration::from_nanos(median.median_ns),
+        })
+    }).collect()
+}
+
+/// Days that ran on another input than in the baseline, such as an example instead of the real input.
+pub fn days_with_other_input(baseline: &BenchRun, current: &BenchRun) -> Vec<usize> {
+    let mut days: Vec<usize> = current.medians.iter()
+        .filter(|median| baseline.medians.iter().any(|baseline_median| baseline_median.day == median.day && baseline_median.input_sha256 != median.input_sha256))
+        .map(|median| median.day)
+        .collect();
+    days.dedup();
+    return days;
+}
+
+/// Prints the changes and flags the ones slower than the threshold. Returns the days with a regression.
+pub fn print_changes(baseline: &BenchRun, changes: &[PhaseChange], threshold_percent: f64) -> Vec<usize> {
+    let dirty = if baseline.dirty { " with uncommitted changes" } else { "" };
+    println!("baseline {}{dirty}, threshold {threshold_percent}%", baseline.commit);
+    println!("{:>5}  {:<7}  {:>12}  {:>12}  {:>9}", "day", "phase", "baseline", "current", "change");
+    let mut regressed_days = Vec::new();
+    for change in changes {
+        let flag = if change.is_regression(threshold_percent) { "  REGRESSION" } else { "" };
+        println!("{:>5}  {:<7}  {:>12}  {:>12}  {:>9}{flag}", change.day, change.phase,
+                 format!("{:.2?}", change.baseline), format!("{:.2?}", change.current), format!("{:+.1}%", change.percent()));
+        if change.is_regression(threshold_percent) && !regressed_days.contains(&change.day) {
+            regressed_days.push(change.day);
+        }
+    }
+    return regressed_days;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::bench_history::{BenchHistory, BenchRun, compare_to_baseline, days_with_other_input, PhaseChange, PhaseMedian};
+    use crate::test_util::temp_dir;
+
+    fn run(commit: &str, medians: &[(usize, &str, u64)]) -> BenchRun {
+        BenchRun {
+            commit: commit.to_string(),
+            dirty: false,
+            debug: false,
+            recorded_at: 0,
+            medians: medians.iter().map(|(day, phase, median_ns)| PhaseMedian {
+                day: *day,
+                phase: phase.to_string(),
+                median_ns: *median_ns,
+                input_sha256: format!("input{day}"),
+            }).collect(),
+        }
+    }
+
+    #[test]
+    fn baseline_is_latest_run_of_another_commit() {
+        let file = temp_dir("bench-history").join("history.jsonl");
+        let mut history = BenchHistory::read(&file).unwrap();
+        history.append(&file, run("aaa111", &[(2, "parse", 100)])).unwrap();
+        history.append(&file, run("bbb222", &[(2, "parse", 200)])).unwrap();
+        history.append(&file, run("ccc333", &[(2, "parse", 300)])).unwrap();
+        history.append(&file, BenchRun { debug: true, ..run("ddd444", &[(2, "parse", 3000)]) }).unwrap();
+
+        let history = BenchHistory::read(&file).unwrap();
+        assert_eq!(history.runs.len(), 4);
+        assert_eq!(history.baseline(None, "ccc333", false).unwrap().commit, "bbb222");
+        assert_eq!(history.baseline(Some("aaa"), "ccc333", false).unwrap().commit, "aaa111");
+        assert!(history.baseline(Some("ddd"), "ccc333", false).is_none());
+        assert_eq!(history.baseline(None, "eee555", true).unwrap().commit, "ddd444");
+        assert!(history.baseline(Some("aaa"), "eee555", true).is_none())
+    }
+
+    #[test]
+    fn flags_phases_slower_than_threshold() {
+        let baseline = run("aaa111", &[(2, "parse", 1000), (8, "part 1", 1000)]);
+        let current = run("bbb222", &[(2, "parse", 1200), (8, "part 1", 1050), (8, "part 2", 5000)]);
+        let changes = compare_to_baseline(&baseline, &current);
+        assert_eq!(changes, vec![
+            PhaseChange { day: 2, phase: "parse".to_string(), baseline: Duration::from_nanos(1000), current: Duration::from_nanos(1200) },
+            PhaseChange { day: 8, phase: "part 1".to_string(), baseline: Duration::from_nanos(1000), current: Duration::from_nanos(1050) },
+        ]);
+        assert!(changes[0].is_regression(10.0));
+        assert!(!changes[1].is_regression(10.0))
+    }
+
+    #[test]
+    fn leaves_out_phases_on_other_inputs() {
+        let baseline = run("aaa111", &[(2, "parse", 1000), (8, "part 1", 1000)]);
+        let mut current = run("bbb222", &[(2, "parse", 1200), (8, "part 1", 5000)]);
+        current.medians[1].input_sha256 = "example8".to_string();
+        assert_eq!(compare_to_baseline(&baseline, &current).iter().map(|change| change.day).collect::<Vec<usize>>(), vec![2]);
+        assert_eq!(days_with_other_input(&baseline, &current), vec![8])
+    }
+}
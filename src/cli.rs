@@ -0,0 +1,517 @@
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use advent_of_code_2023::answers::ANSWERS_FILE;
+use advent_of_code_2023::bench::BenchFormat;
+use advent_of_code_2023::bench_history::BENCH_HISTORY_FILE;
+use advent_of_code_2023::config::{CONFIG_FILE, read_config};
+use advent_of_code_2023::dashboard::README_FILE;
+use advent_of_code_2023::inputs::{InputKind, InputResolver};
+use advent_of_code_2023::isolate::Isolation;
+use advent_of_code_2023::output::OutputFormat;
+use advent_of_code_2023::runner::Task;
+use advent_of_code_2023::serve::DEFAULT_ADDRESS;
+use advent_of_code_2023::solution::{Day, DAYS, find_day, PARTS};
+use advent_of_code_2023::submit::HISTORY_FILE;
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Parser)]
+#[command(about = "Solutions for Advent of Code 2023")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Configuration file with the session token, an optional base URL and an optional input directory
+    #[arg(long, global = true, default_value = CONFIG_FILE)]
+    pub config: String,
+
+    /// Directory with the inputs, overrides the AOC_INPUT_DIR environment variable and the configuration
+    #[arg(long, global = true)]
+    pub input_dir: Option<String>,
+}
+
+impl Cli {
+    pub fn input_resolver(&self) -> io::Result<InputResolver> {
+        Ok(InputResolver::from_env(self.input_dir.as_deref(), &read_config(Path::new(&self.config))?))
+    }
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the selected days and print their answers
+    Run(RunArgs),
+    /// Repeatedly run the parse and solve phases of the selected days and report their timings
+    Bench(BenchArgs),
+    /// Benchmark the selected days and flag the phases that got slower than in a recorded baseline run
+    Regress(RegressArgs),
+    /// Run all implementations of the selected parts on the same input and check that they agree
+    Compare(CompareArgs),
+    /// Run the selected days on their real inputs and compare the answers with the answers file
+    Verify(VerifyArgs),
+    /// Create the module and input files of a new day and register it
+    NewDay(NewDayArgs),
+    /// Download the puzzle input of a day into the input directory, unless it is already there
+    Fetch(FetchArgs),
+    /// Solve a part on the real input and submit the answer, unless earlier attempts already rule it out
+    Submit(SubmitArgs),
+    /// Write the examples of a saved puzzle page to the input directory and their expected answers to the example answers file
+    Extract(ExtractArgs),
+    /// Re-run the example tests and the real input of a day whenever its source or input files change
+    Watch(WatchArgs),
+    /// Load the parsed input of a day and answer queries about it
+    Repl(ReplArgs),
+    /// Answer POST /day/{n}/part/{m} requests with the answer to the puzzle input in the body and its timings as JSON
+    Serve(ServeArgs),
+    /// Regenerate the star table in the readme from the registry, the examples and the answers file, best run in a release build
+    Readme(ReadmeArgs),
+}
+
+#[derive(Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[arg(long, value_enum, default_value_t = FormatArg::Text)]
+    pub format: FormatArg,
+
+    /// Number of days run at the same time, defaults to the available parallelism
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    pub jobs: Option<u32>,
+
+    /// Print the intermediate steps of the solvers to stderr
+    #[arg(short, long)]
+    pub verbose: bool,
+
+    /// Only trace this day, may be repeated, defaults to all selected days
+    #[arg(long, value_parser = parse_day)]
+    pub trace_day: Vec<usize>,
+
+    /// Write the intermediate steps of the solvers to this file in the Chrome trace format
+    #[arg(long)]
+    pub chrome_trace: Option<String>,
+
+    /// Run each day in a child process that is stopped after this many seconds
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["verbose", "chrome_trace"])]
+    pub timeout: Option<u64>,
+
+    /// Run each day in a child process that cannot allocate more than this many megabytes
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["verbose", "chrome_trace"])]
+    pub memory_limit: Option<u64>,
+}
+
+impl RunArgs {
+    pub fn jobs(&self) -> usize {
+        match self.jobs {
+            Some(jobs) => jobs as usize,
+            None => thread::available_parallelism().map_or(1, |jobs| jobs.get()),
+        }
+    }
+
+    pub fn traced_days(&self, tasks: &[Task]) -> Vec<usize> {
+        if !self.verbose && self.chrome_trace.is_none() {
+            return Vec::new();
+        }
+        if !self.trace_day.is_empty() {
+            return self.trace_day.clone();
+        }
+        return tasks.iter().map(|task| task.day.day()).collect();
+    }
+
+    pub fn isolation(&self) -> Option<Isolation> {
+        if self.timeout.is_none() && self.memory_limit.is_none() {
+            return None;
+        }
+        Some(Isolation::new(self.timeout.map(Duration::from_secs), self.memory_limit.map(|megabytes| megabytes << 20)))
+    }
+}
+
+/// Command line names of [OutputFormat].
+#[derive(ValueEnum, Copy, Clone)]
+pub enum FormatArg {
+    Text,
+    /// Text with the parse and solve times
+    Timed,
+    Json,
+    Csv,
+}
+
+impl From<FormatArg> for OutputFormat {
+    fn from(format: FormatArg) -> OutputFormat {
+        match format {
+            FormatArg::Text => OutputFormat::Text,
+            FormatArg::Timed => OutputFormat::Timed,
+            FormatArg::Json => OutputFormat::Json,
+            FormatArg::Csv => OutputFormat::Csv,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// How often each phase is run
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+
+    #[arg(long, value_enum, default_value_t = BenchFormatArg::Table)]
+    pub format: BenchFormatArg,
+
+    /// Append the median times to the benchmark history, tagged with the current git commit
+    #[arg(long)]
+    pub record: bool,
+
+    /// File with the recorded benchmark runs
+    #[arg(long, default_value = BENCH_HISTORY_FILE)]
+    pub history: String,
+}
+
+/// Command line names of [BenchFormat].
+#[derive(ValueEnum, Copy, Clone)]
+pub enum BenchFormatArg {
+    Table,
+    Csv,
+}
+
+impl From<BenchFormatArg> for BenchFormat {
+    fn from(format: BenchFormatArg) -> BenchFormat {
+        match format {
+            BenchFormatArg::Table => BenchFormat::Table,
+            BenchFormatArg::Csv => BenchFormat::Csv,
+        }
+    }
+}
+
+#[derive(Args)]
+pub struct RegressArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// How often each phase is run
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+
+    /// Commit, or a prefix of it, whose latest recorded run is the baseline, defaults to the latest run of another commit
+    #[arg(long)]
+    pub baseline: Option<String>,
+
+    /// Percentage by which a median may grow before it counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    pub threshold: f64,
+
+    /// Also append this run to the benchmark history
+    #[arg(long)]
+    pub record: bool,
+
+    /// File with the recorded benchmark runs
+    #[arg(long, default_value = BENCH_HISTORY_FILE)]
+    pub history: String,
+}
+
+#[derive(Args)]
+pub struct CompareArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// How often each implementation is run, the median time is reported
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    pub iterations: u32,
+}
+
+#[derive(Args)]
+pub struct VerifyArgs {
+    /// Day to verify, every registered day if omitted
+    #[arg(long, value_parser = parse_day)]
+    pub day: Option<usize>,
+
+    /// File with the expected answers
+    #[arg(long, default_value = ANSWERS_FILE)]
+    pub answers: String,
+
+    /// Part as DAY.PART whose real input is too slow to run, may be repeated
+    #[arg(long, value_parser = parse_day_part)]
+    pub skip: Vec<(usize, usize)>,
+}
+
+impl VerifyArgs {
+    pub fn tasks(&self, resolver: &InputResolver) -> io::Result<Vec<Task>> {
+        let mut tasks = Selection {
+            day: self.day,
+            part: None,
+            all: self.day.is_none(),
+            input: None,
+            example: false,
+        }.tasks(resolver)?;
+        for task in &mut tasks {
+            task.parts.retain(|part| !self.skip.contains(&(task.day.day(), *part)));
+        }
+        tasks.retain(|task| !task.parts.is_empty());
+        return Ok(tasks);
+    }
+}
+
+#[derive(Args)]
+pub struct NewDayArgs {
+    /// Day to create
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+}
+
+#[derive(Args)]
+pub struct FetchArgs {
+    /// Day to download
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+}
+
+#[derive(Args)]
+pub struct ExtractArgs {
+    /// Day the puzzle page belongs to
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+    pub day: u8,
+
+    /// Locally saved HTML page of the puzzle description
+    #[arg(long)]
+    pub page: String,
+}
+
+#[derive(Args)]
+pub struct WatchArgs {
+    /// Day to watch
+    #[arg(long, value_parser = parse_day)]
+    pub day: usize,
+
+    /// Milliseconds between two polls of the watched files
+    #[arg(long, default_value_t = 500)]
+    pub interval_ms: u64,
+
+    /// Seconds after which the run on the real input is stopped, so that the watch goes on with later changes
+    #[arg(long, default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    pub timeout: u64,
+}
+
+#[derive(Args)]
+pub struct ReplArgs {
+    /// Day to explore
+    #[arg(long, value_parser = parse_day)]
+    pub day: usize,
+
+    /// Input file to load instead of the day's input, `-` reads stdin
+    #[arg(long, conflicts_with = "example")]
+    pub input: Option<String>,
+
+    /// Load the example of part 1 instead of the real input
+    #[arg(long)]
+    pub example: bool,
+}
+
+impl ReplArgs {
+    pub fn input_file(&self, resolver: &InputResolver) -> io::Result<String> {
+        let path = match (&self.input, self.example) {
+            (Some(input), _) => return Ok(input.clone()),
+            (None, true) => resolver.resolve(self.day, InputKind::Example { part: 1 })?,
+            (None, false) => resolver.resolve(self.day, InputKind::Real)?,
+        };
+        return Ok(path.display().to_string());
+    }
+}
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// Address to listen on
+    #[arg(long, default_value = DEFAULT_ADDRESS)]
+    pub address: String,
+}
+
+#[derive(Args)]
+pub struct ReadmeArgs {
+    /// Readme file whose star table is replaced
+    #[arg(long, default_value = README_FILE)]
+    pub readme: String,
+
+    /// Part as DAY.PART whose real input is too slow to run, may be repeated
+    #[arg(long, value_parser = parse_day_part)]
+    pub skip: Vec<(usize, usize)>,
+}
+
+#[derive(Args)]
+pub struct SubmitArgs {
+    /// Day to submit
+    #[arg(long, value_parser = parse_day)]
+    pub day: usize,
+
+    /// Part to submit
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: u8,
+
+    /// File recording all submitted answers
+    #[arg(long, default_value = HISTORY_FILE)]
+    pub history: String,
+}
+
+impl SubmitArgs {
+    pub fn task(&self, resolver: &InputResolver) -> io::Result<Task> {
+        Ok(Task {
+            day: find_day(self.day).unwrap(),
+            parts: vec![self.part as usize],
+            input_file: resolver.resolve(self.day, InputKind::Real)?.display().to_string(),
+        })
+    }
+}
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("days").required(true).args(["day", "all"])))]
+pub struct Selection {
+    /// Day to run
+    #[arg(long, value_parser = parse_day)]
+    pub day: Option<usize>,
+
+    /// Part to run, both parts if omitted
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// Run every registered day
+    #[arg(long)]
+    pub all: bool,
+
+    /// Input file to use instead of the day's input, `-` reads the standard input
+    #[arg(long, conflicts_with_all = ["all", "example"])]
+    pub input: Option<String>,
+
+    /// Use the example input of each part
+    #[arg(long)]
+    pub example: bool,
+}
+
+impl Selection {
+    pub fn tasks(&self, resolver: &InputResolver) -> io::Result<Vec<Task>> {
+        let days: Vec<&'static dyn Day> = match self.day {
+            Some(day) => vec![find_day(day).unwrap()],
+            None => DAYS.to_vec(),
+        };
+        let parts: Vec<usize> = match self.part {
+            Some(part) => vec![part as usize],
+            None => PARTS.to_vec(),
+        };
+        let mut tasks: Vec<Task> = Vec::new();
+        for day in days {
+            for part in &parts {
+                let input_file = self.input_file_for(resolver, day.day(), *part)?;
+                match tasks.last_mut() {
+                    Some(task) if task.day.day() == day.day() && task.input_file == input_file =>
+                        task.parts.push(*part),
+                    _ => tasks.push(Task {
+                        day,
+                        parts: vec![*part],
+                        input_file,
+                    }),
+                }
+            }
+        }
+        return Ok(tasks);
+    }
+
+    fn input_file_for(&self, resolver: &InputResolver, day: usize, part: usize) -> io::Result<String> {
+        if let Some(input) = &self.input {
+            return Ok(input.clone());
+        }
+        let kind = if self.example { InputKind::Example { part } } else { InputKind::Real };
+        return Ok(resolver.resolve(day, kind)?.display().to_string());
+    }
+}
+
+fn parse_day(value: &str) -> Result<usize, String> {
+    let day: usize = value.parse().map_err(|_| format!("`{value}` is not a day"))?;
+    match find_day(day) {
+        Some(_) => Ok(day),
+        None => Err(format!("there is no solution for day {day}")),
+    }
+}
+
+fn parse_day_part(value: &str) -> Result<(usize, usize), String> {
+    let Some((day, part)) = value.split_once('.') else {
+        return Err(format!("`{value}` is not DAY.PART"));
+    };
+    let part: usize = part.parse().map_err(|_| format!("`{part}` is not a part"))?;
+    if !PARTS.contains(&part) {
+        return Err(format!("there is no part {part}"));
+    }
+    return Ok((parse_day(day)?, part));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use advent_of_code_2023::inputs::InputResolver;
+    use clap::Parser;
+
+    use crate::cli::{Cli, Command};
+
+    fn selected(args: &[&str]) -> Vec<(usize, Vec<usize>, String)> {
+        let resolver = InputResolver::new("res");
+        let tasks = match Cli::try_parse_from(args).unwrap().command {
+            Command::Run(run_args) => run_args.selection.tasks(&resolver),
+            Command::Bench(bench_args) => bench_args.selection.tasks(&resolver),
+            Command::Verify(verify_args) => verify_args.tasks(&resolver),
+            _ => panic!("command does not select tasks"),
+        }.unwrap();
+        tasks.iter().map(|task| (task.day.day(), task.parts.clone(), task.input_file.clone())).collect()
+    }
+
+    #[test]
+    fn selects_single_part_with_input_override() {
+        assert_eq!(selected(&["aoc", "run", "--day", "5", "--part", "2", "--input", "other.txt"]),
+                   vec![(5, vec![2], "other.txt".to_string())])
+    }
+
+    #[test]
+    fn selects_example_files_for_all_days() {
+        let tasks = selected(&["aoc", "run", "--all", "--example"]);
+        assert_eq!(tasks.len(), 10);
+        assert!(tasks.contains(&(1, vec![2], "res/aoc1-2-example.txt".to_string())));
+        assert!(tasks.contains(&(4, vec![1, 2], "res/aoc4-example.txt".to_string())))
+    }
+
+    #[test]
+    fn bench_uses_same_selection() {
+        assert_eq!(selected(&["aoc", "bench", "--day", "6", "--iterations", "3"]),
+                   vec![(6, vec![1, 2], "res/aoc6.txt".to_string())])
+    }
+
+    #[test]
+    fn verify_defaults_to_all_real_inputs() {
+        let tasks = selected(&["aoc", "verify"]);
+        assert_eq!(tasks.len(), 8);
+        assert!(tasks.contains(&(8, vec![1, 2], "res/aoc8.txt".to_string())))
+    }
+
+    #[test]
+    fn verify_skips_slow_parts() {
+        let tasks = selected(&["aoc", "verify", "--skip", "5.2", "--skip", "8.2", "--skip", "6.1", "--skip", "6.2"]);
+        assert_eq!(tasks.len(), 7);
+        assert!(tasks.contains(&(5, vec![1], "res/aoc5.txt".to_string())));
+        assert!(tasks.contains(&(8, vec![1], "res/aoc8.txt".to_string())))
+    }
+
+    #[test]
+    fn input_dir_and_config_are_global() {
+        let cli = Cli::try_parse_from(["aoc", "run", "--all", "--input-dir", "elsewhere", "--config", "missing.toml"]).unwrap();
+        let resolver = cli.input_resolver().unwrap();
+        assert_eq!(resolver.dir, PathBuf::from("elsewhere"));
+        let Command::Run(run_args) = cli.command else {
+            panic!("not a run command");
+        };
+        assert!(run_args.selection.tasks(&resolver).is_err())
+    }
+
+    #[test]
+    fn rejects_unknown_day_and_missing_selection() {
+        assert!(Cli::try_parse_from(["aoc", "run", "--day", "26"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run"]).is_err());
+        assert!(Cli::try_parse_from(["aoc", "run", "--all", "--input", "other.txt"]).is_err())
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use crate::bench::{measure, Stats};
+use crate::runner::Task;
+
+/// Answer and median solve time of one implementation of a part.
+pub struct ImplementationRun {
+    pub implementation: &'static str,
+    pub answer: usize,
+    pub median: Duration,
+}
+
+/// All implementations of one part, run on the same parsed input.
+pub struct Comparison {
+    pub day: usize,
+    pub part: usize,
+    pub input: String,
+    pub runs: Vec<ImplementationRun>,
+}
+
+impl Comparison {
+    pub fn agrees(&self) -> bool {
+        self.runs.iter().all(|run| run.answer == self.runs[0].answer)
+    }
+}
+
+/// Runs every implementation of the selected parts that have more than one, each `iterations` times.
+pub fn compare_tasks(tasks: &[Task], iterations: usize) -> Vec<Comparison> {
+    let mut comparisons = Vec::new();
+    for task in tasks {
+        let parts: Vec<usize> = task.parts.iter().copied().filter(|part| task.day.implementations(*part).len() > 1).collect();
+        if parts.is_empty() {
+            continue;
+        }
+        let input = task.parse(&task.read());
+        for part in parts {
+            let runs = task.day.implementations(part).into_iter().map(|implementation| {
+                let (answer, samples) = measure(iterations, || task.day.solve_with(part, implementation, input.as_ref()).unwrap());
+                ImplementationRun {
+                    implementation,
+                    answer,
+                    median: Stats::from_samples(&samples).median,
+                }
+            }).collect();
+            comparisons.push(Comparison {
+                day: task.day.day(),
+                part,
+                input: task.input_file.clone(),
+                runs,
+            });
+        }
+    }
+    return comparisons;
+}
+
+/// Prints the answer of every implementation and its speed relative to the default one.
+/// Returns whether all implementations of each part agree.
+pub fn print_comparisons(comparisons: &[Comparison]) -> bool {
+    if comparisons.is_empty() {
+        println!("no selected part has more than one implementation");
+    }
+    for comparison in comparisons {
+        let verdict = if comparison.agrees() { "agree" } else { "DISAGREE" };
+        println!("AoC {}.{} on {}: implementations {verdict}", comparison.day, comparison.part, comparison.input);
+        let default_median = comparison.runs[0].median.as_secs_f64();
+        for run in &comparison.runs {
+            let speedup = default_median / run.median.as_secs_f64().max(f64::MIN_POSITIVE);
+            println!("  {:<12}  {:>16}  {:>12}  {:>8}", run.implementation, run.answer, format!("{:.2?}", run.median), format!("{speedup:.2}x"));
+        }
+    }
+    return comparisons.iter().all(|comparison| comparison.agrees());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compare::compare_tasks;
+    use crate::runner::Task;
+    use crate::solution::find_day;
+
+    #[test]
+    fn compares_all_implementations_of_parts_with_alternatives() {
+        let tasks = vec![
+            Task { day: find_day(5).unwrap(), parts: vec![1, 2], input_file: "res/aoc5-example.txt".to_string() },
+            Task { day: find_day(6).unwrap(), parts: vec![1, 2], input_file: "res/aoc6-example.txt".to_string() },
+        ];
+        let comparisons = compare_tasks(&tasks, 2);
+        let compared: Vec<(usize, usize, Vec<&str>, Vec<usize>)> = comparisons.iter().map(|comparison| (
+            comparison.day,
+            comparison.part,
+            comparison.runs.iter().map(|run| run.implementation).collect(),
+            comparison.runs.iter().map(|run| run.answer).collect(),
+        )).collect();
+        assert_eq!(compared, vec![
+            (5, 2, vec!["default", "ranges"], vec![46, 46]),
+            (6, 1, vec!["default", "closed-form"], vec![288, 288]),
+            (6, 2, vec!["default", "closed-form"], vec![71503, 71503]),
+        ]);
+        assert!(comparisons.iter().all(|comparison| comparison.agrees()))
+    }
+}
@@ -0,0 +1,75 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Local, not checked in configuration with `key = "value"` lines.
+pub const CONFIG_FILE: &str = ".aoc.toml";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+#[derive(Default, PartialEq, Eq, Debug)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: Option<String>,
+    pub input_dir: Option<String>,
+}
+
+impl Config {
+    pub fn base_url(&self) -> &str {
+        self.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL).trim_end_matches('/')
+    }
+}
+
+/// Reads the configuration, a missing file is an empty configuration.
+pub fn read_config(file: &Path) -> io::Result<Config> {
+    match fs::read_to_string(file) {
+        Ok(data) => parse_config(&data).map_err(|message| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {message}", file.display()))),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(error) => Err(error),
+    }
+}
+
+static ENTRY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"^(?<key>\w+)\s*=\s*"(?<value>[^"]*)"$"#).unwrap());
+
+fn parse_config(data: &str) -> Result<Config, String> {
+    let mut config = Config::default();
+    for line in data.lines().map(|line| line.trim()) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some(matched_entry) = ENTRY_REGEX.captures(line) else {
+            return Err(format!("cannot parse line: {line}"));
+        };
+        let value = Some(matched_entry.name("value").unwrap().as_str().to_string());
+        match matched_entry.name("key").unwrap().as_str() {
+            "session" => config.session = value,
+            "base_url" => config.base_url = value,
+            "input_dir" => config.input_dir = value,
+            key => return Err(format!("unknown key {key}")),
+        }
+    }
+    return Ok(config);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::{Config, DEFAULT_BASE_URL, parse_config};
+
+    #[test]
+    fn parses_known_keys() {
+        let config = parse_config("# local settings\nsession = \"abc123\"\nbase_url = \"http://localhost:8080/\"\ninput_dir = \"../inputs\"\n").unwrap();
+        assert_eq!(config.session, Some("abc123".to_string()));
+        assert_eq!(config.base_url(), "http://localhost:8080");
+        assert_eq!(config.input_dir, Some("../inputs".to_string()));
+        assert_eq!(Config::default().base_url(), DEFAULT_BASE_URL)
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(parse_config("sesion = \"abc123\"").is_err());
+        assert!(parse_config("session = abc123").is_err())
+    }
+}
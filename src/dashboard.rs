@@ -0,0 +1,142 @@
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::time::Duration;
+
+use crate::answers::{Answers, check, Verdict};
+use crate::fs_util::read_or_panic;
+use crate::inputs::{InputKind, InputResolver};
+use crate::runner::{run_parallel, Task, TaskEvent};
+use crate::solution::{DAYS, PARTS};
+
+pub const README_FILE: &str = "README.md";
+
+const TABLE_START: &str = "<!-- stars:start -->";
+const TABLE_END: &str = "<!-- stars:end -->";
+
+pub enum RealRun {
+    Skipped,
+    Failed,
+    Finished { verdict: Verdict, runtime: Duration },
+}
+
+pub struct PartStatus {
+    pub day: usize,
+    pub part: usize,
+    /// Whether the registered day solves the example of the part to the expected answer.
+    pub solved: bool,
+    pub real_run: RealRun,
+}
+
+/// Collects the status of every registered part. The real input of the parts in `skip` is not run.
+pub fn collect_statuses(resolver: &InputResolver, answers: &Answers, example_answers: &Answers, skip: &[(usize, usize)]) -> Vec<PartStatus> {
+    let tasks: Vec<Task> = DAYS.iter().flat_map(|day| PARTS.iter().map(|part| Task {
+        day: *day,
+        parts: vec![*part],
+        input_file: resolver.real_path(day.day()).display().to_string(),
+    })).collect();
+    let mut statuses: Vec<PartStatus> = tasks.iter().map(|task| {
+        let (day, part) = (task.day.day(), task.parts[0]);
+        let example_file = resolver.resolve(day, InputKind::Example { part });
+        let solved = example_answers.expected(day, part).is_some_and(|expected| {
+            let Ok(example_file) = example_file else {
+                return false;
+            };
+            catch_unwind(AssertUnwindSafe(|| task.day.solve_input(part, &read_or_panic(example_file)))).is_ok_and(|answer| answer == Some(expected))
+        });
+        PartStatus {
+            day,
+            part,
+            solved,
+            real_run: RealRun::Skipped,
+        }
+    }).collect();
+
+    let run: Vec<Task> = tasks.into_iter().filter(|task| !skip.contains(&(task.day.day(), task.parts[0]))).collect();
+    run_parallel(&run, 1, None, |task, event| {
+        let status = statuses.iter_mut().find(|status| status.day == task.day.day() && status.part == task.parts[0]).unwrap();
+        status.real_run = match event {
+            TaskEvent::Record(record) => RealRun::Finished {
+                verdict: check(record.answer, answers.expected(record.day, record.part)),
+                runtime: Duration::from_nanos((record.parse_ns + record.solve_ns) as u64),
+            },
+            _ => RealRun::Failed,
+        };
+    });
+    return statuses;
+}
+
+pub fn format_table(statuses: &[PartStatus]) -> String {
+    let mut table = String::from("| Day | Part | Solved | Verified | Runtime | Source |\n|---|---|---|---|---|---|\n");
+    for status in statuses {
+        let (verified, runtime) = match &status.real_run {
+            RealRun::Skipped => ("–".to_string(), "not run".to_string()),
+            RealRun::Failed => ("❌".to_string(), "failed".to_string()),
+            RealRun::Finished { verdict: Verdict::Correct, runtime } => ("✅".to_string(), format!("{runtime:.2?}")),
+            RealRun::Finished { verdict: Verdict::Wrong { .. }, runtime } => ("❌".to_string(), format!("{runtime:.2?}")),
+            RealRun::Finished { verdict: Verdict::Unknown, runtime } => ("–".to_string(), format!("{runtime:.2?}")),
+        };
+        table.push_str(&format!("| [{day}](https://adventofcode.com/2023/day/{day}) | {} | {} | {verified} | {runtime} | [src/aoc{day}.rs](src/aoc{day}.rs) |\n",
+                                status.part, if status.solved { "⭐" } else { " " }, day = status.day));
+    }
+    return table;
+}
+
+/// Replaces the table between the star markers of the readme, or appends it with the markers if they are missing.
+pub fn update_readme(readme: &str, table: &str) -> String {
+    let generated = format!("{TABLE_START}\n{table}{TABLE_END}");
+    match (readme.find(TABLE_START), readme.find(TABLE_END)) {
+        (Some(start), Some(end)) if start < end => format!("{}{generated}{}", &readme[..start], &readme[end + TABLE_END.len()..]),
+        _ if readme.is_empty() => format!("# Advent of Code 2023\n\n{generated}\n"),
+        _ => format!("{}\n\n{generated}\n", readme.trim_end()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+
+    use crate::answers::{EXAMPLE_ANSWERS_FILE, parse_answers, read_answers, Verdict};
+    use crate::dashboard::{collect_statuses, format_table, PartStatus, RealRun, update_readme};
+    use crate::inputs::InputResolver;
+    use crate::solution::DAYS;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn formats_one_row_per_part() {
+        let statuses = vec![
+            PartStatus { day: 3, part: 1, solved: true, real_run: RealRun::Finished { verdict: Verdict::Correct, runtime: Duration::from_micros(1500) } },
+            PartStatus { day: 3, part: 2, solved: false, real_run: RealRun::Skipped },
+        ];
+        assert_eq!(format_table(&statuses).lines().skip(2).collect::<Vec<&str>>(), vec![
+            "| [3](https://adventofcode.com/2023/day/3) | 1 | ⭐ | ✅ | 1.50ms | [src/aoc3.rs](src/aoc3.rs) |",
+            "| [3](https://adventofcode.com/2023/day/3) | 2 |   | – | not run | [src/aoc3.rs](src/aoc3.rs) |",
+        ])
+    }
+
+    #[test]
+    fn replaces_only_generated_table() {
+        let readme = update_readme("# Notes\n", "| old |\n");
+        assert_eq!(readme, "# Notes\n\n<!-- stars:start -->\n| old |\n<!-- stars:end -->\n");
+        assert_eq!(update_readme(&readme, "| new |\n"), "# Notes\n\n<!-- stars:start -->\n| new |\n<!-- stars:end -->\n")
+    }
+
+    #[test]
+    fn examples_mark_every_part_with_example_answer_solved() {
+        let answers = parse_answers("[day.6]\npart1 = 1\n").unwrap();
+        let example_answers = read_answers(EXAMPLE_ANSWERS_FILE).unwrap();
+        let skip: Vec<(usize, usize)> = DAYS.iter().flat_map(|day| [(day.day(), 1), (day.day(), 2)]).filter(|part| *part != (6, 1)).collect();
+        let statuses = collect_statuses(&InputResolver::new("res"), &answers, &example_answers, &skip);
+        assert!(statuses.iter().filter(|status| example_answers.expected(status.day, status.part).is_some()).all(|status| status.solved));
+        assert!(statuses.iter().any(|status| matches!(status.real_run, RealRun::Finished { verdict: Verdict::Wrong { expected: 1 }, .. })))
+    }
+
+    #[test]
+    fn unreadable_examples_leave_parts_unsolved() {
+        let dir = temp_dir("dashboard");
+        fs::write(dir.join("aoc1-1-example.txt"), [0xff, 0xfe]).unwrap();
+        let skip: Vec<(usize, usize)> = DAYS.iter().flat_map(|day| [(day.day(), 1), (day.day(), 2)]).collect();
+        let statuses = collect_statuses(&InputResolver::new(&dir), &parse_answers("").unwrap(), &read_answers(EXAMPLE_ANSWERS_FILE).unwrap(), &skip);
+        fs::remove_dir_all(dir).unwrap();
+        assert!(statuses.iter().all(|status| !status.solved))
+    }
+}
@@ -0,0 +1,176 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::answers::update_answers;
+use crate::inputs::InputResolver;
+
+/// Example of one puzzle part as found in its description.
+#[derive(PartialEq, Eq, Debug)]
+pub struct PartExample {
+    pub input: Option<String>,
+    pub expected: Option<usize>,
+}
+
+static ARTICLE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(?s)<article class="day-desc">(?<content>.*?)</article>"#).unwrap());
+static CODE_BLOCK_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)<pre><code>(?<code>.*?)</code></pre>").unwrap());
+static HIGHLIGHTED_NUMBER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<code><em>(?<first>\d+)</em></code>|<em><code>(?<second>\d+)</code></em>").unwrap());
+static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+/// Extracts the last highlighted number of every part description of a saved puzzle page, together with the
+/// last code block before it, so that a part with several examples yields the one the answer belongs to.
+pub fn extract_examples(page: &str) -> Vec<PartExample> {
+    ARTICLE_REGEX.captures_iter(page).map(|matched_article| {
+        let content = matched_article.name("content").unwrap().as_str();
+        let matched_number = HIGHLIGHTED_NUMBER_REGEX.captures_iter(content).last();
+        let expected = matched_number.as_ref().map(|matched_number|
+            matched_number.name("first").or(matched_number.name("second")).unwrap().as_str().parse().unwrap()
+        );
+        let answer_start = matched_number.map_or(content.len(), |matched_number| matched_number.get(0).unwrap().start());
+        let code_blocks: Vec<_> = CODE_BLOCK_REGEX.captures_iter(content).collect();
+        let matched_code = code_blocks.iter().rev().find(|matched_code| matched_code.get(0).unwrap().end() <= answer_start)
+            .or(code_blocks.first());
+        let input = matched_code.map(|matched_code|
+            decode_entities(&TAG_REGEX.replace_all(matched_code.name("code").unwrap().as_str(), "")).trim_end().to_string()
+        );
+        PartExample {
+            input,
+            expected,
+        }
+    }).collect()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Header of a new example answers file.
+const EXAMPLE_ANSWERS_HEADER: &str = "# Expected answers for the example inputs aocN*-example.txt next to this file, checked by the day tests.\n";
+
+/// Writes the examples of a saved puzzle page into the input directory. A single example is shared by
+/// both parts, differing examples are written per part. The expected answers are set in the example
+/// answers file of the input directory, leaving its other lines as they are. Returns the written files.
+pub fn write_examples(resolver: &InputResolver, day: usize, page: &str) -> io::Result<Vec<PathBuf>> {
+    let examples = extract_examples(page);
+    let Some(first_input) = examples.first().and_then(|example| example.input.as_ref()) else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "no example found in the puzzle page"));
+    };
+    let mut written = Vec::new();
+    let shared = examples.iter().all(|example| example.input.as_ref().is_none_or(|input| input == first_input));
+    if shared {
+        written.push(resolver.shared_example_path(day));
+        fs::write(&written[0], first_input)?;
+    } else {
+        let mut input = first_input;
+        for (index, example) in examples.iter().enumerate() {
+            input = example.input.as_ref().unwrap_or(input);
+            let example_file = resolver.part_example_path(day, index + 1);
+            fs::write(&example_file, input)?;
+            written.push(example_file);
+        }
+    }
+
+    let answers_file = resolver.example_answers_path();
+    let data = match fs::read_to_string(&answers_file) {
+        Ok(data) => data,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => EXAMPLE_ANSWERS_HEADER.to_string(),
+        Err(error) => return Err(error),
+    };
+    let expected: Vec<(usize, usize)> = examples.iter().enumerate()
+        .filter_map(|(index, example)| example.expected.map(|expected| (index + 1, expected)))
+        .collect();
+    let updated = update_answers(&data, day, &expected)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {error}", answers_file.display())))?;
+    fs::write(&answers_file, updated)?;
+    written.push(answers_file);
+    return Ok(written);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::answers::parse_answers;
+    use crate::extract::{extract_examples, PartExample, write_examples};
+    use crate::inputs::InputResolver;
+    use crate::test_util::temp_dir;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 3: Gear Ratios ---</h2>
+<p>Here is an example engine schematic:</p>
+<pre><code>467..114..
+...*......
+617*......</code></pre>
+<p>In this schematic, two numbers are <em>not</em> part numbers; adding the rest gives <code><em>4361</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Consider the same schematic again:</p>
+<pre><code>467..114..
+...*......
+617*......
+</code></pre>
+<p>Adding up all of the gear ratios produces <code><em>467835</em></code>.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn extracts_code_blocks_and_highlighted_answers() {
+        let examples = extract_examples(PAGE);
+        let input = Some("467..114..\n...*......\n617*......".to_string());
+        assert_eq!(examples, vec![
+            PartExample { input: input.clone(), expected: Some(4361) },
+            PartExample { input, expected: Some(467835) },
+        ]);
+        let decoded = extract_examples(r#"<article class="day-desc"><pre><code>a &lt;<em>b</em>&gt; &amp;c</code></pre></article>"#);
+        assert_eq!(decoded[0].input, Some("a <b> &c".to_string()));
+        assert_eq!(decoded[0].expected, None)
+    }
+
+    #[test]
+    fn pairs_answer_with_its_own_example() {
+        let page = r#"<article class="day-desc"><h2>--- Day 8: Haunted Wasteland ---</h2>
+<pre><code>RL
+
+AAA = (BBB, CCC)</code></pre>
+<p>Starting with <code>AAA</code>, you need to look up the next element ... reaching <code>ZZZ</code> takes <code><em>2</em></code> steps.</p>
+<p>Of course, you might not find <code>ZZZ</code> right away:</p>
+<pre><code>LLR
+
+AAA = (BBB, BBB)</code></pre>
+<p>Starting at <code>AAA</code>, follow the left/right instructions. How many steps are required? <code><em>6</em></code> steps.</p>
+</article>"#;
+        assert_eq!(extract_examples(page), vec![
+            PartExample { input: Some("LLR\n\nAAA = (BBB, BBB)".to_string()), expected: Some(6) },
+        ])
+    }
+
+    #[test]
+    fn writes_shared_example_and_merges_answers() {
+        let root = temp_dir("extract");
+        let answers_file = root.join("example-answers.toml");
+        fs::write(&answers_file, "# Checked in header\n\n[day.1]\npart1 = 142\n\n[day.3]\npart1 = 1 # wrong\n").unwrap();
+
+        let written = write_examples(&InputResolver::new(&root), 3, PAGE).unwrap();
+        assert_eq!(written, vec![root.join("aoc3-example.txt"), answers_file.clone()]);
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), "467..114..\n...*......\n617*......");
+        assert_eq!(fs::read_to_string(&answers_file).unwrap(),
+                   "# Checked in header\n\n[day.1]\npart1 = 142\n\n[day.3]\npart1 = 4361 # wrong\npart2 = 467835\n");
+        assert!(write_examples(&InputResolver::new(&root), 4, "<html></html>").is_err())
+    }
+
+    #[test]
+    fn starts_missing_answers_file_with_header() {
+        let root = temp_dir("extract-new");
+        write_examples(&InputResolver::new(&root), 3, PAGE).unwrap();
+        let answers = parse_answers(&fs::read_to_string(root.join("example-answers.toml")).unwrap()).unwrap();
+        assert_eq!(answers.expected(3, 1), Some(4361));
+        assert_eq!(answers.expected(3, 2), Some(467835))
+    }
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::config::Config;
+
+pub const USER_AGENT: &str = "github.com/svenallers/advent-of-code-2023";
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum FetchOutcome {
+    Cached,
+    Downloaded,
+}
+
+/// Downloads the puzzle input of `day` into `target`, unless a non-empty input is already there.
+pub fn fetch_input(config: &Config, day: usize, target: &Path) -> io::Result<FetchOutcome> {
+    if fs::metadata(target).is_ok_and(|metadata| metadata.len() > 0) {
+        return Ok(FetchOutcome::Cached);
+    }
+    let Some(session) = &config.session else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no session configured"));
+    };
+    let url = format!("{}/2023/day/{day}/input", config.base_url());
+    let input_data = ureq::get(&url)
+        .header("Cookie", format!("session={session}"))
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|error| io::Error::other(format!("cannot download {url}: {error}")))?;
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(target, input_data)?;
+    return Ok(FetchOutcome::Downloaded);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::config::Config;
+    use crate::fetch::{fetch_input, FetchOutcome};
+    use crate::test_util::{MockServer, temp_dir};
+
+    #[test]
+    fn downloads_input_only_once() {
+        let server = MockServer::start(vec![(200, "Time: 7\nDistance: 9\n")]);
+        let config = Config {
+            session: Some("secret".to_string()),
+            base_url: Some(server.base_url.clone()),
+            ..Config::default()
+        };
+        let target = temp_dir("fetch").join("res/aoc6.txt");
+
+        assert_eq!(fetch_input(&config, 6, &target).unwrap(), FetchOutcome::Downloaded);
+        assert_eq!(fetch_input(&config, 6, &target).unwrap(), FetchOutcome::Cached);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "Time: 7\nDistance: 9\n");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2023/day/6/input HTTP/1.1\r\n"));
+        assert!(requests[0].to_lowercase().contains("\r\ncookie: session=secret\r\n"))
+    }
+
+    #[test]
+    fn keeps_target_untouched_on_error() {
+        let server = MockServer::start(vec![(404, "Not Found")]);
+        let config = Config {
+            session: Some("secret".to_string()),
+            base_url: Some(server.base_url.clone()),
+            ..Config::default()
+        };
+        let target = temp_dir("fetch-error").join("aoc9.txt");
+
+        assert!(fetch_input(&config, 9, &target).is_err());
+        assert!(!target.exists());
+        server.requests();
+    }
+}
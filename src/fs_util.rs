@@ -1,9 +1,77 @@
 use std::fs::read_to_string;
+use std::io::{Read, stdin};
 use std::path::Path;
 
+/// Input path that stands for the standard input.
+pub const STDIN: &str = "-";
+
+/// Returns the content of a file compiled into the binary, looked up by its path relative to the crate root,
+/// such as `res/aoc1.txt`. Only the `embed-inputs` feature embeds files.
+pub fn embedded(path: &Path) -> Option<&'static str> {
+    #[cfg(feature = "embed-inputs")]
+    {
+        return embedded_files::EMBEDDED_FILES.iter().find(|(name, _)| Path::new(name) == path).map(|(_, data)| *data);
+    }
+    #[cfg(not(feature = "embed-inputs"))]
+    {
+        let _ = path;
+        return None;
+    }
+}
+
+#[cfg(feature = "embed-inputs")]
+mod embedded_files {
+    include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+}
+
+/// Whether `path` can be read, from the embedded files or the file system.
+pub fn input_exists(path: &Path) -> bool {
+    embedded(path).is_some() || path.is_file()
+}
+
+/// Reads a file, preferring the embedded copy if there is one.
 pub fn read_or_panic(path: impl AsRef<Path>) -> String {
+    if let Some(data) = embedded(path.as_ref()) {
+        return data.to_string();
+    }
     let Ok(data) = read_to_string(path) else {
         panic!("unable to read file")
     };
     return data;
-}
\ No newline at end of file
+}
+
+pub fn read_from_or_panic(mut reader: impl Read) -> String {
+    let mut data = String::new();
+    if let Err(error) = reader.read_to_string(&mut data) {
+        panic!("unable to read input: {error}")
+    }
+    return data;
+}
+
+pub fn read_input_or_panic(input: &str) -> String {
+    if input == STDIN {
+        read_from_or_panic(stdin().lock())
+    } else {
+        read_or_panic(input)
+    }
+}
+
+#[cfg(all(test, feature = "embed-inputs"))]
+mod tests {
+    use std::fs;
+    use std::path::Path;
+
+    use crate::fs_util::{embedded, input_exists};
+
+    #[test]
+    fn embeds_all_inputs() {
+        for entry in fs::read_dir("res").unwrap() {
+            let path = Path::new("res").join(entry.unwrap().file_name());
+            if path.extension().is_some_and(|extension| extension == "txt") {
+                assert_eq!(embedded(&path), Some(fs::read_to_string(&path).unwrap().as_str()), "{}", path.display());
+            }
+        }
+        assert!(input_exists(Path::new("res/aoc8-2-example.txt")));
+        assert_eq!(embedded(Path::new("elsewhere/aoc1.txt")), None)
+    }
+}
@@ -0,0 +1,33 @@
+use std::fs;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::time::SystemTime;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Reads the entries of a local history file with one JSON object per line, a missing file is an empty history.
+pub fn read_entries<T: DeserializeOwned>(file: &Path) -> io::Result<Vec<T>> {
+    let data = match fs::read_to_string(file) {
+        Ok(data) => data,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(error) => return Err(error),
+    };
+    return data.lines().filter(|line| !line.trim().is_empty()).map(|line|
+        serde_json::from_str(line).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {error}", file.display())))
+    ).collect();
+}
+
+/// Appends one entry as a JSON line, creating the history file if needed.
+pub fn append_entry<T: Serialize>(file: &Path, entry: &T) -> io::Result<()> {
+    let mut history_file = OpenOptions::new().create(true).append(true).open(file)?;
+    writeln!(history_file, "{}", serde_json::to_string(entry).unwrap())?;
+    return Ok(());
+}
+
+/// Seconds since the Unix epoch, the timestamp of history entries.
+pub fn unix_now() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs()
+}
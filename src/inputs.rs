@@ -0,0 +1,120 @@
+use std::env;
+use std::io;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::fs_util::input_exists;
+
+/// Environment variable with the input directory, used if no `--input-dir` flag is given.
+pub const INPUT_DIR_ENV: &str = "AOC_INPUT_DIR";
+
+pub const DEFAULT_INPUT_DIR: &str = "res";
+
+/// Name of the file with the expected answers of the examples, next to the example inputs.
+pub const EXAMPLE_ANSWERS_NAME: &str = "example-answers.toml";
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InputKind {
+    Real,
+    Example { part: usize },
+}
+
+/// Finds the inputs of all days in one directory: `aocN.txt` for the real input, `aocN-P-example.txt`
+/// for an example of one part and `aocN-example.txt` for an example shared by both parts.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InputResolver {
+    pub dir: PathBuf,
+}
+
+impl InputResolver {
+    pub fn new(dir: impl Into<PathBuf>) -> InputResolver {
+        InputResolver {
+            dir: dir.into(),
+        }
+    }
+
+    /// Takes the directory from the first given of the flag, the environment variable and the config, `res` otherwise.
+    pub fn from_sources(flag: Option<&str>, env_dir: Option<String>, config: &Config) -> InputResolver {
+        let dir = flag.map(|dir| dir.to_string())
+            .or(env_dir)
+            .or(config.input_dir.clone())
+            .unwrap_or(DEFAULT_INPUT_DIR.to_string());
+        return InputResolver::new(dir);
+    }
+
+    pub fn from_env(flag: Option<&str>, config: &Config) -> InputResolver {
+        InputResolver::from_sources(flag, env::var(INPUT_DIR_ENV).ok(), config)
+    }
+
+    pub fn real_path(&self, day: usize) -> PathBuf {
+        self.dir.join(format!("aoc{day}.txt"))
+    }
+
+    pub fn part_example_path(&self, day: usize, part: usize) -> PathBuf {
+        self.dir.join(format!("aoc{day}-{part}-example.txt"))
+    }
+
+    pub fn shared_example_path(&self, day: usize) -> PathBuf {
+        self.dir.join(format!("aoc{day}-example.txt"))
+    }
+
+    pub fn example_answers_path(&self) -> PathBuf {
+        self.dir.join(EXAMPLE_ANSWERS_NAME)
+    }
+
+    /// Paths the input may have, in the order they are tried.
+    pub fn candidates(&self, day: usize, kind: InputKind) -> Vec<PathBuf> {
+        match kind {
+            InputKind::Real => vec![self.real_path(day)],
+            InputKind::Example { part } => vec![self.part_example_path(day, part), self.shared_example_path(day)],
+        }
+    }
+
+    /// Returns the first existing candidate, embedded or on disk, or an error listing all tried paths.
+    pub fn resolve(&self, day: usize, kind: InputKind) -> io::Result<PathBuf> {
+        let candidates = self.candidates(day, kind);
+        if let Some(found) = candidates.iter().find(|candidate| input_exists(candidate)) {
+            return Ok(found.clone());
+        }
+        let tried: Vec<String> = candidates.iter().map(|candidate| candidate.display().to_string()).collect();
+        let input = match kind {
+            InputKind::Real => format!("input of day {day}"),
+            InputKind::Example { part } => format!("example of day {day} part {part}"),
+        };
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("cannot find the {input}, tried {}", tried.join(", "))));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::config::Config;
+    use crate::inputs::{InputKind, InputResolver};
+
+    #[test]
+    fn flag_overrides_environment_and_config() {
+        let config = Config {
+            input_dir: Some("from-config".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(InputResolver::from_sources(Some("from-flag"), Some("from-env".to_string()), &config).dir, PathBuf::from("from-flag"));
+        assert_eq!(InputResolver::from_sources(None, Some("from-env".to_string()), &config).dir, PathBuf::from("from-env"));
+        assert_eq!(InputResolver::from_sources(None, None, &config).dir, PathBuf::from("from-config"));
+        assert_eq!(InputResolver::from_sources(None, None, &Config::default()).dir, PathBuf::from("res"))
+    }
+
+    #[test]
+    fn prefers_part_specific_examples() {
+        let resolver = InputResolver::new("res");
+        assert_eq!(resolver.resolve(8, InputKind::Example { part: 2 }).unwrap(), PathBuf::from("res/aoc8-2-example.txt"));
+        assert_eq!(resolver.resolve(5, InputKind::Example { part: 2 }).unwrap(), PathBuf::from("res/aoc5-example.txt"));
+        assert_eq!(resolver.resolve(5, InputKind::Real).unwrap(), PathBuf::from("res/aoc5.txt"))
+    }
+
+    #[test]
+    fn lists_tried_paths() {
+        let error = InputResolver::new("missing").resolve(3, InputKind::Example { part: 1 }).unwrap_err();
+        assert_eq!(error.to_string(), "cannot find the example of day 3 part 1, tried missing/aoc3-1-example.txt, missing/aoc3-example.txt")
+    }
+}
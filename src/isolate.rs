@@ -0,0 +1,221 @@
+use std::env;
+use std::io::{BufRead, BufReader, Read};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::runner::{panic_message, Task, TaskEvent};
+use crate::solution::find_day;
+
+/// Environment variable through which a child process receives its task.
+pub const CHILD_TASK_ENV: &str = "AOC_CHILD_TASK";
+
+#[derive(Serialize, Deserialize)]
+struct ChildTask {
+    day: usize,
+    parts: Vec<usize>,
+    input_file: String,
+}
+
+/// Runs each task in a child process of the current executable, which is killed when it runs longer than
+/// `timeout` and cannot allocate more than `memory_limit` bytes of address space.
+pub struct Isolation {
+    pub timeout: Option<Duration>,
+    pub memory_limit: Option<u64>,
+    /// Arguments of the child process, which finds its task in [CHILD_TASK_ENV] and runs it with [run_child_task].
+    pub child_args: Vec<String>,
+}
+
+impl Isolation {
+    pub fn new(timeout: Option<Duration>, memory_limit: Option<u64>) -> Isolation {
+        Isolation {
+            timeout,
+            memory_limit,
+            child_args: Vec::new(),
+        }
+    }
+
+    /// Runs the task in a child process and hands over its events, ending with a [TaskEvent::TimedOut] or
+    /// [TaskEvent::OutOfMemory] if the child exceeded a limit.
+    pub fn run(&self, task: &Task, mut on_event: impl FnMut(TaskEvent)) {
+        let child_task = ChildTask {
+            day: task.day.day(),
+            parts: task.parts.clone(),
+            input_file: task.input_file.clone(),
+        };
+        let mut command = Command::new(env::current_exe().unwrap());
+        command.args(&self.child_args)
+            .env(CHILD_TASK_ENV, serde_json::to_string(&child_task).unwrap())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(memory_limit) = self.memory_limit {
+            if let Err(message) = limit_memory(&mut command, memory_limit) {
+                on_event(TaskEvent::Failed(message));
+                return;
+            }
+        }
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(error) => {
+                on_event(TaskEvent::Failed(format!("cannot start child process: {error}")));
+                return;
+            }
+        };
+
+        let stdout = child.stdout.take().unwrap();
+        let mut stderr = child.stderr.take().unwrap();
+        let (sender, receiver) = channel();
+        // Lines that are no events, like the ones of a test harness running the child, are skipped.
+        thread::spawn(move || for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if let Ok(event) = serde_json::from_str::<TaskEvent>(&line) {
+                if sender.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+        let stderr_reader = thread::spawn(move || {
+            let mut output = String::new();
+            let _ = stderr.read_to_string(&mut output);
+            output
+        });
+
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let mut reported_failure = false;
+        loop {
+            let received = match deadline {
+                Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(event) => {
+                    reported_failure |= !matches!(event, TaskEvent::Record(_));
+                    on_event(event);
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    on_event(TaskEvent::TimedOut(self.timeout.unwrap()));
+                    return;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        let status = child.wait().unwrap();
+        let stderr = stderr_reader.join().unwrap();
+        if status.success() || reported_failure {
+            return;
+        }
+        if self.memory_limit.is_some() && ran_out_of_memory(&stderr) {
+            on_event(TaskEvent::OutOfMemory);
+        } else {
+            let message = match stderr.lines().last() {
+                Some(last_line) => format!("child process ended with {status}: {last_line}"),
+                None => format!("child process ended with {status}"),
+            };
+            on_event(TaskEvent::Failed(message));
+        }
+    }
+}
+
+/// Whether the child failed to allocate, which Rust reports before aborting. The address space limit never
+/// makes the kernel kill the child, so a kill by a signal came from someone else and is reported as such.
+fn ran_out_of_memory(stderr: &str) -> bool {
+    stderr.contains("memory allocation of")
+}
+
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, memory_limit: u64) -> Result<(), String> {
+    use std::io;
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: memory_limit as libc::rlim_t,
+        rlim_max: memory_limit as libc::rlim_t,
+    };
+    // SAFETY: setrlimit is async-signal-safe, and the closure only touches the forked child.
+    unsafe {
+        command.pre_exec(move || match libc::setrlimit(libc::RLIMIT_AS, &limit) {
+            0 => Ok(()),
+            _ => Err(io::Error::last_os_error()),
+        });
+    }
+    return Ok(());
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _memory_limit: u64) -> Result<(), String> {
+    Err("memory limits are only supported on unix".to_string())
+}
+
+/// Runs the task handed over by a parent process, printing each event as a line of JSON. Returns whether
+/// this process is such a child.
+pub fn run_child_task() -> bool {
+    let Ok(data) = env::var(CHILD_TASK_ENV) else {
+        return false;
+    };
+    let child_task: ChildTask = serde_json::from_str(&data).unwrap();
+    let task = Task {
+        day: find_day(child_task.day).unwrap(),
+        parts: child_task.parts,
+        input_file: child_task.input_file,
+    };
+    let print_event = |event: TaskEvent| println!("{}", serde_json::to_string(&event).unwrap());
+    let result = catch_unwind(AssertUnwindSafe(|| task.run(|record| print_event(TaskEvent::Record(record)))));
+    if let Err(payload) = result {
+        print_event(TaskEvent::Failed(panic_message(payload.as_ref())));
+    }
+    return true;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+    use std::time::Duration;
+
+    use crate::isolate::{Isolation, run_child_task};
+    use crate::runner::{Task, TaskEvent};
+    use crate::solution::find_day;
+
+    /// Entry point of the children started by the other tests, does nothing in a normal test run.
+    #[test]
+    fn child_process() {
+        if run_child_task() {
+            process::exit(0);
+        }
+    }
+
+    fn isolated_events(day: usize, part: usize, input_file: &str, timeout: Option<Duration>, memory_limit: Option<u64>) -> Vec<TaskEvent> {
+        let isolation = Isolation {
+            child_args: ["isolate::tests::child_process", "--exact", "--nocapture", "--quiet", "--test-threads=1"].map(String::from).to_vec(),
+            ..Isolation::new(timeout, memory_limit)
+        };
+        let task = Task {
+            day: find_day(day).unwrap(),
+            parts: vec![part],
+            input_file: input_file.to_string(),
+        };
+        let mut events = Vec::new();
+        isolation.run(&task, |event| events.push(event));
+        return events;
+    }
+
+    #[test]
+    fn reports_answers_and_panics_of_the_child() {
+        let events = isolated_events(6, 2, "res/aoc6-example.txt", Some(Duration::from_secs(60)), None);
+        assert!(matches!(&events[..], [TaskEvent::Record(record)] if record.answer == 71503));
+        let events = isolated_events(8, 1, "res/aoc1-1-example.txt", None, None);
+        assert!(matches!(&events[..], [TaskEvent::Failed(_)]))
+    }
+
+    #[test]
+    fn stops_child_beyond_limits() {
+        let timeout = Duration::from_millis(500);
+        assert_eq!(isolated_events(8, 2, "res/aoc8.txt", Some(timeout), None), vec![TaskEvent::TimedOut(timeout)]);
+        assert_eq!(isolated_events(4, 2, "res/aoc4.txt", None, Some(256 << 20)), vec![TaskEvent::OutOfMemory])
+    }
+}
@@ -0,0 +1,49 @@
+//! Solutions for Advent of Code 2023.
+//!
+//! Every `aocN` module exposes the parsed model of its puzzle input, a `parse_input_data` parser and
+//! the solver functions of both parts. [solution::DAYS] lists all days behind the common [solution::Day] interface.
+//!
+//! ```
+//! use advent_of_code_2023::aoc7::{calculate_total_winnings, parse_input_data};
+//!
+//! let hands = parse_input_data("32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483");
+//! assert_eq!(calculate_total_winnings(&hands), 6440);
+//! ```
+
+pub mod alloc_stats;
+pub mod answers;
+pub mod aoc1;
+pub mod aoc2;
+pub mod aoc3;
+pub mod aoc4;
+pub mod aoc5;
+pub mod aoc6;
+pub mod aoc7;
+pub mod aoc8;
+pub mod bench;
+pub mod bench_history;
+pub mod compare;
+pub mod config;
+pub mod dashboard;
+pub mod extract;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod fetch;
+pub mod fs_util;
+pub mod history;
+pub mod inputs;
+pub mod isolate;
+pub mod output;
+pub mod parse_util;
+pub mod repl;
+pub mod runner;
+pub mod scaffold;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod serve;
+pub mod solution;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod submit;
+pub mod trace;
+pub mod wasm;
+pub mod watch;
+#[cfg(test)]
+mod test_util;
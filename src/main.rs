@@ -1,32 +1,245 @@
-use crate::aoc7::{aoc_7_1, aoc_7_2};
-use crate::aoc8::{aoc_8_1, aoc_8_2};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
 
-mod aoc1;
-mod aoc2;
-mod aoc3;
-mod aoc4;
-mod aoc5;
-mod aoc6;
-mod aoc7;
-mod aoc8;
-mod fs_util;
-mod parse_util;
+use advent_of_code_2023::answers::{ANSWERS_FILE, read_answers, verify_tasks};
+use advent_of_code_2023::bench::{bench_tasks, print_benchmarks};
+use advent_of_code_2023::bench_history::{BenchHistory, BenchRun, compare_to_baseline, current_commit, current_run, days_with_other_input, print_changes};
+use advent_of_code_2023::compare::{compare_tasks, print_comparisons};
+use advent_of_code_2023::config::read_config;
+use advent_of_code_2023::dashboard::{collect_statuses, format_table, update_readme};
+use advent_of_code_2023::extract::write_examples;
+use advent_of_code_2023::fetch::{fetch_input, FetchOutcome};
+use advent_of_code_2023::fs_util::read_input_or_panic;
+use advent_of_code_2023::history::unix_now;
+use advent_of_code_2023::isolate::run_child_task;
+use advent_of_code_2023::repl::run_repl;
+use advent_of_code_2023::runner::run_tasks;
+use advent_of_code_2023::scaffold::scaffold_day;
+use advent_of_code_2023::serve::serve;
+use advent_of_code_2023::solution::find_day;
+use advent_of_code_2023::submit::{submit, SubmitOutcome};
+use advent_of_code_2023::trace::{chrome_trace_json, finish, start};
+use advent_of_code_2023::watch::watch_day;
+use clap::Parser;
+use tiny_http::Server;
 
-fn main() {
-    // println!("AoC 1.1: {}", aoc_1_1());
-    // println!("AoC 1.2: {}", aoc_1_2());
-    // println!("AoC 2.1: {}", aoc_2_1());
-    // println!("AoC 2.2: {}", aoc_2_2());
-    // println!("AoC 3.1: {}", aoc_3_1());
-    // println!("AoC 3.2: {}", aoc_3_2());
-    // println!("AoC 4.1: {}", aoc_4_1());
-    // println!("AoC 4.2: {}", aoc_4_2());
-    // println!("AoC 5.1: {}", aoc_5_1());
-    // println!("AoC 5.2: {}", aoc_5_2());
-    // println!("AoC 6.1: {}", aoc_6_1());
-    // println!("AoC 6.2: {}", aoc_6_2());
-    // println!("AoC 7.1: {}", aoc_7_1());
-    // println!("AoC 7.2: {}", aoc_7_2());
-    // println!("AoC 8.1: {}", aoc_8_1());
-    println!("AoC 8.2: {}", aoc_8_2());
+use crate::cli::{Cli, Command};
+
+mod cli;
+
+fn main() -> ExitCode {
+    if run_child_task() {
+        return ExitCode::SUCCESS;
+    }
+    let cli = Cli::parse();
+    let resolver = match cli.input_resolver() {
+        Ok(resolver) => resolver,
+        Err(error) => {
+            eprintln!("cannot read {}: {error}", cli.config);
+            return ExitCode::FAILURE;
+        }
+    };
+    match cli.command {
+        Command::Run(args) => {
+            let Some(tasks) = or_report(args.selection.tasks(&resolver)) else {
+                return ExitCode::FAILURE;
+            };
+            start(&args.traced_days(&tasks), args.verbose, args.chrome_trace.is_some());
+            let succeeded = run_tasks(&tasks, args.format.into(), args.jobs(), args.isolation().as_ref());
+            let events = finish();
+            if let Some(chrome_trace) = &args.chrome_trace {
+                if let Err(error) = fs::write(chrome_trace, chrome_trace_json(&events)) {
+                    eprintln!("cannot write {chrome_trace}: {error}");
+                    return ExitCode::FAILURE;
+                }
+            }
+            if !succeeded {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Bench(args) => {
+            let Some(tasks) = or_report(args.selection.tasks(&resolver)) else {
+                return ExitCode::FAILURE;
+            };
+            let benchmarks = bench_tasks(&tasks, args.iterations as usize);
+            print_benchmarks(&benchmarks, args.format.into());
+            if args.record {
+                let recorded = current_run(Path::new("."), &benchmarks, unix_now())
+                    .and_then(|run| BenchHistory::read(Path::new(&args.history))?.append(Path::new(&args.history), run));
+                if let Err(error) = recorded {
+                    eprintln!("cannot record the benchmarks in {}: {error}", args.history);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Regress(args) => {
+            let Some(tasks) = or_report(args.selection.tasks(&resolver)) else {
+                return ExitCode::FAILURE;
+            };
+            let mut history = match BenchHistory::read(Path::new(&args.history)) {
+                Ok(history) => history,
+                Err(error) => {
+                    eprintln!("cannot read {}: {error}", args.history);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let (commit, dirty) = match current_commit(Path::new(".")) {
+                Ok(current) => current,
+                Err(error) => {
+                    eprintln!("cannot determine the current commit: {error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+            let Some(baseline) = history.baseline(args.baseline.as_deref(), &commit, cfg!(debug_assertions)).cloned() else {
+                eprintln!("no baseline run of a {profile} build in {}, record one with `bench --record`", args.history);
+                return ExitCode::FAILURE;
+            };
+            let current = BenchRun::new(commit, dirty, unix_now(), &bench_tasks(&tasks, args.iterations as usize));
+            for day in days_with_other_input(&baseline, &current) {
+                eprintln!("day {day} ran on another input than in the baseline, it is not compared");
+            }
+            let changes = compare_to_baseline(&baseline, &current);
+            if changes.is_empty() {
+                eprintln!("no phase of the baseline {} ran on the same input", baseline.commit);
+                return ExitCode::FAILURE;
+            }
+            let regressed_days = print_changes(&baseline, &changes, args.threshold);
+            if args.record {
+                if let Err(error) = history.append(Path::new(&args.history), current) {
+                    eprintln!("cannot record the benchmarks in {}: {error}", args.history);
+                    return ExitCode::FAILURE;
+                }
+            }
+            if !regressed_days.is_empty() {
+                let days: Vec<String> = regressed_days.iter().map(|day| day.to_string()).collect();
+                eprintln!("regressions in day {}", days.join(", "));
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Compare(args) => {
+            let Some(tasks) = or_report(args.selection.tasks(&resolver)) else {
+                return ExitCode::FAILURE;
+            };
+            if !print_comparisons(&compare_tasks(&tasks, args.iterations as usize)) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Verify(args) => {
+            let Some(tasks) = or_report(args.tasks(&resolver)) else {
+                return ExitCode::FAILURE;
+            };
+            let answers = match read_answers(&args.answers) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if !verify_tasks(&tasks, &answers) {
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::NewDay(args) => match scaffold_day(Path::new("."), &resolver, args.day as usize) {
+            Ok(created) => created.iter().for_each(|file| println!("created {}", file.display())),
+            Err(error) => {
+                eprintln!("cannot create day {}: {error}", args.day);
+                return ExitCode::FAILURE;
+            }
+        },
+        Command::Fetch(args) => {
+            let target = resolver.real_path(args.day as usize);
+            match read_config(Path::new(&cli.config)).and_then(|config| fetch_input(&config, args.day as usize, &target)) {
+                Ok(FetchOutcome::Cached) => println!("{} is already there", target.display()),
+                Ok(FetchOutcome::Downloaded) => println!("downloaded {}", target.display()),
+                Err(error) => {
+                    eprintln!("cannot fetch day {}: {error}", args.day);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Submit(args) => {
+            let Some(task) = or_report(args.task(&resolver)) else {
+                return ExitCode::FAILURE;
+            };
+            let mut answer = None;
+            task.run(|record| answer = Some(record.answer));
+            let answer = answer.unwrap();
+            let outcome = read_config(Path::new(&cli.config))
+                .and_then(|config| submit(&config, Path::new(&args.history), args.day, args.part as usize, answer, unix_now()));
+            match outcome {
+                Ok(SubmitOutcome::RuledOut(reason)) => println!("AoC {}.{}: {answer} not submitted, {reason}", args.day, args.part),
+                Ok(SubmitOutcome::Submitted(feedback)) => println!("AoC {}.{}: {answer} submitted, {feedback:?}", args.day, args.part),
+                Err(error) => {
+                    eprintln!("cannot submit AoC {}.{}: {error}", args.day, args.part);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Extract(args) => {
+            match fs::read_to_string(&args.page).and_then(|page| write_examples(&resolver, args.day as usize, &page)) {
+                Ok(written) => written.iter().for_each(|file| println!("wrote {}", file.display())),
+                Err(error) => {
+                    eprintln!("cannot extract examples of day {} from {}: {error}", args.day, args.page);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        Command::Watch(args) => {
+            if let Err(error) = watch_day(&resolver.dir, args.day, Duration::from_millis(args.interval_ms), Duration::from_secs(args.timeout)) {
+                eprintln!("cannot watch day {}: {error}", args.day);
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Repl(args) => {
+            let Some(input_file) = or_report(args.input_file(&resolver)) else {
+                return ExitCode::FAILURE;
+            };
+            let day = find_day(args.day).unwrap();
+            let input = day.parse(&read_input_or_panic(&input_file));
+            if let Err(error) = run_repl(day, input.as_ref(), io::stdin().lock(), io::stdout()) {
+                eprintln!("repl of day {} failed: {error}", args.day);
+                return ExitCode::FAILURE;
+            }
+        }
+        Command::Serve(args) => {
+            let server = match Server::http(&args.address) {
+                Ok(server) => server,
+                Err(error) => {
+                    eprintln!("cannot listen on {}: {error}", args.address);
+                    return ExitCode::FAILURE;
+                }
+            };
+            println!("serving on http://{}", server.server_addr());
+            serve(&server);
+        }
+        Command::Readme(args) => {
+            if cfg!(debug_assertions) {
+                eprintln!("the runtimes in the readme come from a debug build, regenerate it with `cargo run --release -- readme`");
+            }
+            let (answers, example_answers) = match (read_answers(ANSWERS_FILE), read_answers(resolver.example_answers_path())) {
+                (Ok(answers), Ok(example_answers)) => (answers, example_answers),
+                (Err(error), _) | (_, Err(error)) => {
+                    eprintln!("{error}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let statuses = collect_statuses(&resolver, &answers, &example_answers, &args.skip);
+            let readme = fs::read_to_string(&args.readme).unwrap_or_default();
+            if let Err(error) = fs::write(&args.readme, update_readme(&readme, &format_table(&statuses))) {
+                eprintln!("cannot write {}: {error}", args.readme);
+                return ExitCode::FAILURE;
+            }
+            println!("updated {}", args.readme);
+        }
+    }
+    return ExitCode::SUCCESS;
+}
+
+/// Prints the error of a failed input lookup.
+fn or_report<T>(result: io::Result<T>) -> Option<T> {
+    result.map_err(|error| eprintln!("{error}")).ok()
 }
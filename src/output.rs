@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::alloc_stats::AllocStats;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum OutputFormat {
+    Text,
+    /// Text with the parse and solve times
+    Timed,
+    Json,
+    Csv,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RunRecord {
+    pub day: usize,
+    pub part: usize,
+    pub answer: usize,
+    pub input: String,
+    pub input_sha256: String,
+    pub parse_ns: u128,
+    pub solve_ns: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alloc: Option<AllocStats>,
+}
+
+pub fn input_hash(input_data: &str) -> String {
+    Sha256::digest(input_data.as_bytes()).iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Prints records as soon as they are available, except for JSON which needs all of them to close the array.
+pub struct RecordPrinter {
+    format: OutputFormat,
+    pending: Vec<RunRecord>,
+}
+
+impl RecordPrinter {
+    pub fn new(format: OutputFormat) -> RecordPrinter {
+        if format == OutputFormat::Csv {
+            let alloc_columns = if cfg!(feature = "alloc-stats") { ",allocations,allocated_bytes,peak_bytes" } else { "" };
+            println!("day,part,answer,input,input_sha256,parse_ns,solve_ns{alloc_columns}");
+        }
+        RecordPrinter {
+            format,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn print(&mut self, record: &RunRecord) {
+        match self.format {
+            OutputFormat::Text => println!("AoC {}.{}: {}{}", record.day, record.part, record.answer, alloc_suffix(record)),
+            OutputFormat::Timed => println!("AoC {}.{}: {} (parse {:.2?}, solve {:.2?}){}", record.day, record.part, record.answer,
+                                            Duration::from_nanos(record.parse_ns as u64), Duration::from_nanos(record.solve_ns as u64), alloc_suffix(record)),
+            OutputFormat::Csv => println!("{}", csv_line(record)),
+            OutputFormat::Json => self.pending.push(record.clone()),
+        }
+    }
+
+    pub fn finish(self) {
+        if self.format == OutputFormat::Json {
+            println!("{}", serde_json::to_string_pretty(&self.pending).unwrap());
+        }
+    }
+}
+
+fn alloc_suffix(record: &RunRecord) -> String {
+    record.alloc.map(|alloc| format!(" [{alloc}]")).unwrap_or_default()
+}
+
+fn csv_line(record: &RunRecord) -> String {
+    let alloc_columns = record.alloc.map(|alloc| format!(",{},{},{}", alloc.allocations, alloc.allocated_bytes, alloc.peak_bytes)).unwrap_or_default();
+    format!("{},{},{},{},{},{},{}{alloc_columns}", record.day, record.part, record.answer, csv_field(&record.input),
+            record.input_sha256, record.parse_ns, record.solve_ns)
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::{csv_line, input_hash, RunRecord};
+
+    fn record(input: &str) -> RunRecord {
+        RunRecord {
+            day: 7,
+            part: 2,
+            answer: 5905,
+            input: input.to_string(),
+            input_sha256: input_hash(""),
+            parse_ns: 1200,
+            solve_ns: 3400,
+            alloc: None,
+        }
+    }
+
+    #[test]
+    fn input_hash_is_sha256() {
+        assert_eq!(input_hash(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    }
+
+    #[test]
+    fn csv_quotes_input_path_if_needed() {
+        assert_eq!(csv_line(&record("res/aoc7.txt")),
+                   format!("7,2,5905,res/aoc7.txt,{},1200,3400", input_hash("")));
+        assert!(csv_line(&record("my,\"input\".txt")).contains(",\"my,\"\"input\"\".txt\","))
+    }
+
+    #[test]
+    fn json_record_has_all_fields() {
+        let json = serde_json::to_value(record("res/aoc7.txt")).unwrap();
+        assert_eq!(json["day"], 7);
+        assert_eq!(json["answer"], 5905);
+        assert_eq!(json["input"], "res/aoc7.txt");
+        assert_eq!(json["solve_ns"], 3400);
+        assert!(json.get("alloc").is_none())
+    }
+}
@@ -2,4 +2,9 @@ use regex::Captures;
 
 pub fn parse_numbers(capture: &Captures, group_name: &str) -> Vec<usize>{
     capture.name(group_name).unwrap().as_str().split(" ").filter(|num| !num.is_empty()).map(|num| num.parse().unwrap()).collect()
-}
\ No newline at end of file
+}
+
+/// Parses a numeric argument of a REPL query.
+pub fn parse_argument(argument: &str) -> Result<usize, String> {
+    argument.parse().map_err(|_| format!("{argument} is not a number"))
+}
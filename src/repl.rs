@@ -0,0 +1,67 @@
+use std::any::Any;
+use std::io;
+use std::io::{BufRead, Write};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use crate::parse_util::parse_argument;
+use crate::runner::panic_message;
+use crate::solution::Day;
+
+/// Answers queries about the parsed `input` of `day`, one per line, until `quit` or the end of `reader`.
+pub fn run_repl(day: &dyn Day, input: &dyn Any, reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+    let mut lines = reader.lines();
+    loop {
+        write!(writer, "aoc{}> ", day.day())?;
+        writer.flush()?;
+        let Some(line) = lines.next() else {
+            writeln!(writer)?;
+            return Ok(());
+        };
+        let line = line?;
+        let query: Vec<&str> = line.split_whitespace().collect();
+        let reply = match query.as_slice() {
+            [] => continue,
+            ["quit"] | ["exit"] => return Ok(()),
+            ["help"] => Ok(["part N", "help", "quit"].iter().chain(day.queries()).copied().collect::<Vec<&str>>().join("\n")),
+            ["part", part] => parse_argument(part).and_then(|part|
+                evaluate(|| day.solve(part, input).map(|answer| answer.to_string()).ok_or(format!("no part {part}")))
+            ),
+            _ => evaluate(|| day.query(input, &query)),
+        };
+        match reply {
+            Ok(reply) => writeln!(writer, "{reply}")?,
+            Err(message) => writeln!(writer, "error: {message}")?,
+        }
+    }
+}
+
+fn evaluate(query: impl FnOnce() -> Result<String, String>) -> Result<String, String> {
+    catch_unwind(AssertUnwindSafe(query)).unwrap_or_else(|payload| Err(panic_message(payload.as_ref())))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fs_util::read_or_panic;
+    use crate::repl::run_repl;
+    use crate::solution::find_day;
+
+    fn session(day: usize, example_file: &str, queries: &str) -> String {
+        let day = find_day(day).unwrap();
+        let input = day.parse(&read_or_panic(example_file));
+        let mut output = Vec::new();
+        run_repl(day, input.as_ref(), queries.as_bytes(), &mut output).unwrap();
+        return String::from_utf8(output).unwrap();
+    }
+
+    #[test]
+    fn answers_queries_of_days() {
+        assert_eq!(session(2, "res/aoc2-example.txt", "valid 12 13 14\nvalid 12 x 14\n"),
+                   "aoc2> 3 valid games adding up to 8: 1, 2, 5\naoc2> error: x is not a number\naoc2> \n");
+        assert_eq!(session(5, "res/aoc5-example.txt", "location 79\npart 1\nquit\nlocation 14\n"),
+                   "aoc5> 82\naoc5> 35\naoc5> ");
+        assert_eq!(session(7, "res/aoc7-example.txt", "rank KK677\nrank KK67\n"),
+                   "aoc7> TwoPair at rank 3, with jokers TwoPair at rank 2 of 5 hands\naoc7> error: KK67 are not five cards\naoc7> \n");
+        assert_eq!(session(8, "res/aoc8-1-example.txt", "path AAA ZZZ\npath ZZZ AAA\nunknown\n"),
+                   "aoc8> 6 steps: AAA -> BBB -> AAA -> BBB -> AAA -> BBB -> ZZZ\naoc8> error: AAA is unreachable from ZZZ\naoc8> error: unknown query unknown\naoc8> \n")
+    }
+}
@@ -0,0 +1,197 @@
+use std::any::Any;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::channel;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc_stats::measure;
+use crate::fs_util::read_input_or_panic;
+use crate::isolate::Isolation;
+use crate::output::{input_hash, OutputFormat, RecordPrinter, RunRecord};
+use crate::solution::Day;
+use crate::trace::{enabled, record_span};
+
+/// Parts of one day that share the same input file, so that it is parsed only once.
+pub struct Task {
+    pub day: &'static dyn Day,
+    pub parts: Vec<usize>,
+    pub input_file: String,
+}
+
+impl Task {
+    pub fn read(&self) -> String {
+        read_input_or_panic(&self.input_file)
+    }
+
+    pub fn parse(&self, input_data: &str) -> Box<dyn Any> {
+        self.day.parse(input_data)
+    }
+
+    pub fn solve(&self, part: usize, input: &dyn Any) -> usize {
+        self.day.solve(part, input).unwrap_or_else(|| panic!("Day {} has no part {part}", self.day.day()))
+    }
+
+    /// Parses the input once and hands over a record for each part as soon as it is solved.
+    pub fn run(&self, mut on_record: impl FnMut(RunRecord)) {
+        let input_data = self.read();
+        let input_sha256 = input_hash(&input_data);
+        let parse_start = Instant::now();
+        let input = self.parse(&input_data);
+        let parse_ns = parse_start.elapsed().as_nanos();
+        if enabled(self.day.day()) {
+            record_span(self.day.day(), "parse", parse_start);
+        }
+        for part in &self.parts {
+            let solve_start = Instant::now();
+            let (answer, alloc) = measure(|| self.solve(*part, input.as_ref()));
+            if enabled(self.day.day()) {
+                record_span(self.day.day(), &format!("part {part}"), solve_start);
+            }
+            on_record(RunRecord {
+                day: self.day.day(),
+                part: *part,
+                answer,
+                input: self.input_file.clone(),
+                input_sha256: input_sha256.clone(),
+                parse_ns,
+                solve_ns: solve_start.elapsed().as_nanos(),
+                alloc,
+            });
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub enum TaskEvent {
+    Record(RunRecord),
+    Failed(String),
+    /// The task ran longer than the timeout of its [Isolation].
+    TimedOut(Duration),
+    /// The task needed more memory than the limit of its [Isolation].
+    OutOfMemory,
+}
+
+impl TaskEvent {
+    /// The record of a solved part, or why the task failed.
+    pub fn into_record(self) -> Result<RunRecord, String> {
+        match self {
+            TaskEvent::Record(record) => Ok(record),
+            TaskEvent::Failed(message) => Err(message),
+            TaskEvent::TimedOut(timeout) => Err(format!("timed out after {timeout:?}")),
+            TaskEvent::OutOfMemory => Err("out of memory".to_string()),
+        }
+    }
+}
+
+/// Runs the tasks on `jobs` worker threads, each task in a child process if `isolation` is given. Events are
+/// handed to `on_event` in task order, and a panicking task only ends that task with a [TaskEvent::Failed].
+pub fn run_parallel(tasks: &[Task], jobs: usize, isolation: Option<&Isolation>, mut on_event: impl FnMut(&Task, TaskEvent)) {
+    let next_task = AtomicUsize::new(0);
+    let (sender, receiver) = channel::<(usize, Option<TaskEvent>)>();
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, tasks.len().max(1)) {
+            let sender = sender.clone();
+            let next_task = &next_task;
+            scope.spawn(move || loop {
+                let index = next_task.fetch_add(1, Ordering::Relaxed);
+                let Some(task) = tasks.get(index) else {
+                    break;
+                };
+                if let Some(isolation) = isolation {
+                    isolation.run(task, |event| sender.send((index, Some(event))).unwrap());
+                } else {
+                    let result = catch_unwind(AssertUnwindSafe(||
+                        task.run(|record| sender.send((index, Some(TaskEvent::Record(record)))).unwrap())
+                    ));
+                    if let Err(payload) = result {
+                        sender.send((index, Some(TaskEvent::Failed(panic_message(payload.as_ref()))))).unwrap();
+                    }
+                }
+                sender.send((index, None)).unwrap();
+            });
+        }
+        drop(sender);
+
+        let mut pending: Vec<Vec<Option<TaskEvent>>> = tasks.iter().map(|_| Vec::new()).collect();
+        let mut current = 0;
+        for (index, event) in receiver {
+            pending[index].push(event);
+            while current < tasks.len() && !pending[current].is_empty() {
+                let mut finished = false;
+                for event in pending[current].drain(..) {
+                    match event {
+                        Some(event) => on_event(&tasks[current], event),
+                        None => finished = true,
+                    }
+                }
+                if !finished {
+                    break;
+                }
+                current += 1;
+            }
+        }
+    });
+}
+
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Prints the results of all tasks and returns whether none of them failed.
+pub fn run_tasks(tasks: &[Task], format: OutputFormat, jobs: usize, isolation: Option<&Isolation>) -> bool {
+    let mut printer = RecordPrinter::new(format);
+    let mut failures = 0;
+    run_parallel(tasks, jobs, isolation, |task, event| match event.into_record() {
+        Ok(record) => printer.print(&record),
+        Err(message) => {
+            failures += 1;
+            eprintln!("AoC {} on {} failed: {message}", task.day.day(), task.input_file);
+        }
+    });
+    printer.finish();
+    return failures == 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runner::{run_parallel, Task, TaskEvent};
+    use crate::solution::find_day;
+
+    fn task(day: usize, input_file: &str) -> Task {
+        Task {
+            day: find_day(day).unwrap(),
+            parts: vec![1, 2],
+            input_file: input_file.to_string(),
+        }
+    }
+
+    #[test]
+    fn results_are_gathered_in_task_order() {
+        let tasks = vec![task(7, "res/aoc7-example.txt"), task(3, "res/aoc3-example.txt"), task(6, "res/aoc6-example.txt")];
+        let mut answers = Vec::new();
+        run_parallel(&tasks, 3, None, |_, event| if let TaskEvent::Record(record) = event {
+            answers.push((record.day, record.part, record.answer));
+        });
+        assert_eq!(answers, vec![(7, 1, 6440), (7, 2, 5905), (3, 1, 4361), (3, 2, 467835), (6, 1, 288), (6, 2, 71503)])
+    }
+
+    #[test]
+    fn panicking_task_does_not_hide_other_results() {
+        let tasks = vec![task(8, "res/aoc1-1-example.txt"), task(2, "res/aoc2-example.txt")];
+        let mut events = Vec::new();
+        run_parallel(&tasks, 2, None, |task, event| events.push(match event {
+            TaskEvent::Record(record) => format!("{}.{}: {}", record.day, record.part, record.answer),
+            _ => format!("{} failed", task.day.day()),
+        }));
+        assert_eq!(events, vec!["8 failed", "2.1: 8", "2.2: 2286"])
+    }
+}
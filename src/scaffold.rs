@@ -0,0 +1,168 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::inputs::InputResolver;
+
+/// Creates `src/aocN.rs` below `root` from the day template together with empty input files in the
+/// input directory and registers the new day in `src/lib.rs`, `src/solution.rs` and `src/wasm.rs`. The
+/// input files are written first, so that a failure leaves the sources untouched. Returns the created files.
+pub fn scaffold_day(root: &Path, resolver: &InputResolver, day: usize) -> io::Result<Vec<PathBuf>> {
+    let module_file = root.join(format!("src/aoc{day}.rs"));
+    if module_file.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", module_file.display())));
+    }
+    let lib_file = root.join("src/lib.rs");
+    let solution_file = root.join("src/solution.rs");
+    let lib = register_module(&fs::read_to_string(&lib_file)?, day);
+    let solution = register_day(&fs::read_to_string(&solution_file)?, day);
+    let wasm_file = root.join("src/wasm.rs");
+    let wasm = register_parser(&fs::read_to_string(&wasm_file)?, day);
+
+    fs::create_dir_all(&resolver.dir)?;
+    let mut created_inputs = Vec::new();
+    for input_file in [resolver.real_path(day), resolver.shared_example_path(day)] {
+        if !input_file.exists() {
+            fs::write(&input_file, "")?;
+            created_inputs.push(input_file);
+        }
+    }
+    fs::write(&module_file, day_template(day))?;
+    fs::write(&lib_file, lib)?;
+    fs::write(&solution_file, solution)?;
+    fs::write(&wasm_file, wasm)?;
+    return Ok([module_file].into_iter().chain(created_inputs).collect());
+}
+
+fn day_template(day: usize) -> String {
+    format!(r#"use crate::solution::Solution;
+
+pub struct Aoc{day};
+
+impl Solution for Aoc{day} {{
+    type Input = Vec<String>;
+
+    fn day(&self) -> usize {{
+        {day}
+    }}
+
+    fn parse(&self, input_data: &str) -> Vec<String> {{
+        parse_input_data(input_data)
+    }}
+
+    fn part_1(&self, lines: &Vec<String>) -> usize {{
+        solve_part_1(lines)
+    }}
+
+    fn part_2(&self, lines: &Vec<String>) -> usize {{
+        solve_part_2(lines)
+    }}
+}}
+
+pub fn solve_part_1(lines: &[String]) -> usize {{
+    todo!("solve part 1 for {{}} lines", lines.len())
+}}
+
+pub fn solve_part_2(lines: &[String]) -> usize {{
+    todo!("solve part 2 for {{}} lines", lines.len())
+}}
+
+pub fn parse_input_data(input_data: &str) -> Vec<String> {{
+    return input_data.lines().map(|line| line.to_string()).collect();
+}}
+
+#[cfg(test)]
+mod tests {{
+    use crate::aoc{day}::{{parse_input_data, solve_part_1, solve_part_2}};
+    use crate::test_util::{{example_answer, example_input}};
+
+    #[test]
+    fn aoc_{day}_1() {{
+        let lines = parse_input_data(&example_input({day}, 1));
+        assert_eq!(solve_part_1(&lines), example_answer({day}, 1))
+    }}
+
+    #[test]
+    fn aoc_{day}_2() {{
+        let lines = parse_input_data(&example_input({day}, 2));
+        assert_eq!(solve_part_2(&lines), example_answer({day}, 2))
+    }}
+}}
+"#)
+}
+
+static MODULE_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^pub mod aoc(?<day>\d+);$").unwrap());
+static IMPORT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^use crate::aoc(?<day>\d+)::Aoc\d+;$").unwrap());
+static ENTRY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s+&Aoc(?<day>\d+),$").unwrap());
+static PARSER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s+(?<day>\d+) => parse_day\d+,$").unwrap());
+
+fn register_module(lib: &str, day: usize) -> String {
+    insert_sorted(lib, &MODULE_REGEX, day, format!("pub mod aoc{day};"))
+}
+
+fn register_day(solution: &str, day: usize) -> String {
+    let with_import = insert_sorted(solution, &IMPORT_REGEX, day, format!("use crate::aoc{day}::Aoc{day};"));
+    insert_sorted(&with_import, &ENTRY_REGEX, day, format!("    &Aoc{day},"))
+}
+
+fn register_parser(wasm: &str, day: usize) -> String {
+    insert_sorted(wasm, &PARSER_REGEX, day, format!("    {day} => parse_day{day},"))
+}
+
+/// Inserts `line` behind the last line matched by `regex` whose day is lower than `day`.
+fn insert_sorted(source: &str, regex: &Regex, day: usize, line: String) -> String {
+    let mut lines: Vec<String> = source.lines().map(|line| line.to_string()).collect();
+    let matching: Vec<(usize, usize)> = lines.iter().enumerate().filter_map(|(index, line)|
+        regex.captures(line).map(|matched| (index, matched.name("day").unwrap().as_str().parse().unwrap()))
+    ).collect();
+    let Some((first, _)) = matching.first() else {
+        panic!("cannot find where to register day {day}");
+    };
+    let position = matching.iter().filter(|(_, registered)| *registered < day).map(|(index, _)| index + 1).next_back().unwrap_or(*first);
+    lines.insert(position, line);
+    return lines.join("\n") + "\n";
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::inputs::InputResolver;
+    use crate::scaffold::{register_day, register_module, scaffold_day};
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn registers_day_in_order() {
+        assert_eq!(register_module("pub mod aoc1;\npub mod aoc3;\npub mod bench;\n", 2),
+                   "pub mod aoc1;\npub mod aoc2;\npub mod aoc3;\npub mod bench;\n");
+        assert_eq!(register_day("use crate::aoc1::Aoc1;\n\nstatic DAYS: &[&dyn Day] = &[\n    &Aoc1,\n];\n", 9),
+                   "use crate::aoc1::Aoc1;\nuse crate::aoc9::Aoc9;\n\nstatic DAYS: &[&dyn Day] = &[\n    &Aoc1,\n    &Aoc9,\n];\n")
+    }
+
+    #[test]
+    fn scaffolds_day_once() {
+        let root = temp_dir("scaffold");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::copy("src/lib.rs", root.join("src/lib.rs")).unwrap();
+        fs::copy("src/solution.rs", root.join("src/solution.rs")).unwrap();
+        fs::copy("src/wasm.rs", root.join("src/wasm.rs")).unwrap();
+
+        let resolver = InputResolver::new(root.join("inputs"));
+        let created = scaffold_day(&root, &resolver, 9).unwrap();
+        assert_eq!(created, vec![root.join("src/aoc9.rs"), root.join("inputs/aoc9.txt"), root.join("inputs/aoc9-example.txt")]);
+        assert!(fs::read_to_string(root.join("src/aoc9.rs")).unwrap().contains("parse_input_data(&example_input(9, 1))"));
+        assert!(fs::read_to_string(root.join("src/lib.rs")).unwrap().contains("pub mod aoc8;\npub mod aoc9;\n"));
+        assert!(fs::read_to_string(root.join("src/solution.rs")).unwrap().contains("    &Aoc8,\n    &Aoc9,\n"));
+        assert!(fs::read_to_string(root.join("src/wasm.rs")).unwrap().contains("    8 => parse_day8,\n    9 => parse_day9,\n"));
+        assert!(scaffold_day(&root, &resolver, 9).is_err());
+
+        let unusable = InputResolver::new(root.join("src/lib.rs/inputs"));
+        assert!(scaffold_day(&root, &unusable, 10).is_err());
+        assert!(!root.join("src/aoc10.rs").exists());
+        assert!(!fs::read_to_string(root.join("src/lib.rs")).unwrap().contains("pub mod aoc10;"));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
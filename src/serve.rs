@@ -0,0 +1,159 @@
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::thread;
+use std::time::Instant;
+
+use serde::Serialize;
+use serde_json::json;
+use tiny_http::{Header, Response, Server};
+
+use crate::output::input_hash;
+use crate::runner::panic_message;
+use crate::solution::{DAYS, find_day, PARTS};
+
+/// Address the `serve` command listens on, only reachable from the local machine.
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:8023";
+
+/// Status and JSON body of a response.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Reply {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Reply {
+    fn json(status: u16, value: &impl Serialize) -> Reply {
+        Reply {
+            status,
+            body: serde_json::to_string(value).unwrap(),
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Reply {
+        Reply::json(status, &json!({ "error": message }))
+    }
+}
+
+#[derive(Serialize)]
+struct SolveReply {
+    day: usize,
+    part: usize,
+    answer: usize,
+    input_sha256: String,
+    parse_ns: u128,
+    solve_ns: u128,
+}
+
+/// Routes a request: `GET /days` lists the registered days and `POST /day/{n}/part/{m}` solves the part on
+/// the puzzle input in the body.
+pub fn handle(method: &str, url: &str, body: &str) -> Reply {
+    let path = url.split('?').next().unwrap().trim_end_matches('/');
+    let segments: Vec<&str> = path.split('/').skip(1).collect();
+    match (method, &segments[..]) {
+        ("GET", ["days"]) => {
+            let days: Vec<_> = DAYS.iter().map(|day| json!({ "day": day.day(), "parts": PARTS })).collect();
+            Reply::json(200, &days)
+        }
+        ("POST", ["day", day, "part", part]) => solve(day, part, body),
+        (_, ["days"] | ["day", _, "part", _]) => Reply::error(405, &format!("{method} is not allowed on {path}")),
+        _ => Reply::error(404, &format!("there is nothing at {path}")),
+    }
+}
+
+fn solve(day: &str, part: &str, input_data: &str) -> Reply {
+    let Some(day) = day.parse().ok().and_then(find_day) else {
+        return Reply::error(404, &format!("there is no solution for day {day}"));
+    };
+    let Some(part) = part.parse().ok().filter(|part| PARTS.contains(part)) else {
+        return Reply::error(404, &format!("there is no part {part}"));
+    };
+    let solved = catch_unwind(AssertUnwindSafe(|| {
+        let parse_start = Instant::now();
+        let input = day.parse(input_data);
+        let parse_ns = parse_start.elapsed().as_nanos();
+        let solve_start = Instant::now();
+        let answer = day.solve(part, input.as_ref()).unwrap();
+        (answer, parse_ns, solve_start.elapsed().as_nanos())
+    }));
+    match solved {
+        Ok((answer, parse_ns, solve_ns)) => Reply::json(200, &SolveReply {
+            day: day.day(),
+            part,
+            answer,
+            input_sha256: input_hash(input_data),
+            parse_ns,
+            solve_ns,
+        }),
+        Err(payload) => Reply::error(422, &format!("cannot solve the input: {}", panic_message(payload.as_ref()))),
+    }
+}
+
+/// Answers requests until the server is unblocked, each on its own thread so that a slow part does not hold up
+/// the others.
+pub fn serve(server: &Server) {
+    thread::scope(|scope| for mut request in server.incoming_requests() {
+        scope.spawn(move || {
+            let mut body = String::new();
+            let reply = match request.as_reader().read_to_string(&mut body) {
+                Ok(_) => handle(request.method().as_str(), request.url(), &body),
+                Err(error) => Reply::error(400, &format!("cannot read the body: {error}")),
+            };
+            let response = Response::from_string(reply.body)
+                .with_status_code(reply.status)
+                .with_header(Header::from_bytes("Content-Type", "application/json").unwrap())
+                .with_header(Header::from_bytes("Access-Control-Allow-Origin", "*").unwrap());
+            if let Err(error) = request.respond(response) {
+                eprintln!("cannot send the response: {error}");
+            }
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use serde_json::Value;
+    use tiny_http::Server;
+
+    use crate::fs_util::read_or_panic;
+    use crate::serve::{handle, serve};
+
+    fn json(body: &str) -> Value {
+        serde_json::from_str(body).unwrap()
+    }
+
+    #[test]
+    fn routes_requests() {
+        let reply = handle("POST", "/day/7/part/1", &read_or_panic("res/aoc7-example.txt"));
+        assert_eq!(reply.status, 200);
+        assert_eq!(json(&reply.body)["answer"], 6440);
+        assert_eq!(json(&handle("GET", "/days", "").body)[7]["day"], 8);
+        assert_eq!(handle("POST", "/day/26/part/1", "").status, 404);
+        assert_eq!(handle("POST", "/day/7/part/3", "").status, 404);
+        assert_eq!(handle("GET", "/day/7/part/1", "").status, 405);
+        assert_eq!(handle("POST", "/", "").status, 404);
+        let reply = handle("POST", "/day/6/part/1", "no races");
+        assert_eq!(reply.status, 422);
+        assert!(json(&reply.body)["error"].as_str().unwrap().starts_with("cannot solve the input"))
+    }
+
+    #[test]
+    fn serves_answers_with_timings() {
+        let server = Arc::new(Server::http("127.0.0.1:0").unwrap());
+        let url = format!("http://{}/day/6/part/2", server.server_addr());
+        let serving = thread::spawn({
+            let server = server.clone();
+            move || serve(&server)
+        });
+
+        let mut response = ureq::post(&url).send(read_or_panic("res/aoc6-example.txt")).unwrap();
+        assert_eq!(response.headers()["Content-Type"], "application/json");
+        let reply = json(&response.body_mut().read_to_string().unwrap());
+        assert_eq!(reply["answer"], 71503);
+        assert!(reply["parse_ns"].is_u64() && reply["solve_ns"].is_u64());
+
+        server.unblock();
+        serving.join().unwrap()
+    }
+}
@@ -0,0 +1,205 @@
+use std::any::Any;
+use std::io;
+use std::io::Read;
+
+use serde::Serialize;
+
+use crate::aoc1::Aoc1;
+use crate::aoc2::Aoc2;
+use crate::aoc3::Aoc3;
+use crate::aoc4::Aoc4;
+use crate::aoc5::Aoc5;
+use crate::aoc6::Aoc6;
+use crate::aoc7::Aoc7;
+use crate::aoc8::Aoc8;
+
+pub const PARTS: [usize; 2] = [1, 2];
+
+/// Name of the implementation of a part given by [Solution::part_1] and [Solution::part_2].
+pub const DEFAULT_IMPLEMENTATION: &str = "default";
+
+/// Further implementation of a part, such as the naive one an optimization started from. The `compare`
+/// command checks that it agrees with the default implementation.
+pub struct Alternative<I: 'static> {
+    pub part: usize,
+    pub name: &'static str,
+    pub solve: fn(&I) -> usize,
+}
+
+pub trait Solution: Sync {
+    type Input: Serialize + 'static;
+
+    fn day(&self) -> usize;
+
+    fn parse(&self, input_data: &str) -> Self::Input;
+
+    fn part_1(&self, input: &Self::Input) -> usize;
+
+    fn part_2(&self, input: &Self::Input) -> usize;
+
+    fn alternatives(&self) -> &'static [Alternative<Self::Input>] {
+        &[]
+    }
+
+    /// Usage of the queries understood by [Solution::query], e.g. `location SEED`.
+    fn queries(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Answers a REPL query about the parsed input. The first word selects the query, the others are its arguments.
+    fn query(&self, _input: &Self::Input, query: &[&str]) -> Result<String, String> {
+        Err(format!("unknown query {}", query[0]))
+    }
+}
+
+/// Type erased view on a [Solution], so that all days fit into one registry.
+pub trait Day: Sync {
+    fn day(&self) -> usize;
+
+    fn parse(&self, input_data: &str) -> Box<dyn Any>;
+
+    fn solve(&self, part: usize, input: &dyn Any) -> Option<usize>;
+
+    /// Names of all implementations of a part, starting with [DEFAULT_IMPLEMENTATION].
+    fn implementations(&self, part: usize) -> Vec<&'static str>;
+
+    fn solve_with(&self, part: usize, implementation: &str, input: &dyn Any) -> Option<usize>;
+
+    fn queries(&self) -> &'static [&'static str];
+
+    fn query(&self, input: &dyn Any, query: &[&str]) -> Result<String, String>;
+
+    /// Renders the parsed input as JSON, so that it can be looked at outside of Rust.
+    fn to_json(&self, input: &dyn Any) -> String;
+
+    fn parse_from(&self, reader: &mut dyn Read) -> io::Result<Box<dyn Any>> {
+        let mut input_data = String::new();
+        reader.read_to_string(&mut input_data)?;
+        Ok(self.parse(&input_data))
+    }
+
+    /// Parses the given input and solves one part of it, without touching the filesystem.
+    fn solve_input(&self, part: usize, input_data: &str) -> Option<usize> {
+        self.solve(part, self.parse(input_data).as_ref())
+    }
+}
+
+impl<S: Solution> Day for S {
+    fn day(&self) -> usize {
+        Solution::day(self)
+    }
+
+    fn parse(&self, input_data: &str) -> Box<dyn Any> {
+        Box::new(Solution::parse(self, input_data))
+    }
+
+    fn solve(&self, part: usize, input: &dyn Any) -> Option<usize> {
+        let Some(input) = input.downcast_ref::<S::Input>() else {
+            panic!("input of day {} was not parsed by it", Solution::day(self))
+        };
+        match part {
+            1 => Some(self.part_1(input)),
+            2 => Some(self.part_2(input)),
+            _ => None,
+        }
+    }
+
+    fn implementations(&self, part: usize) -> Vec<&'static str> {
+        if !PARTS.contains(&part) {
+            return Vec::new();
+        }
+        let alternatives = self.alternatives().iter().filter(|alternative| alternative.part == part);
+        return [DEFAULT_IMPLEMENTATION].into_iter().chain(alternatives.map(|alternative| alternative.name)).collect();
+    }
+
+    fn solve_with(&self, part: usize, implementation: &str, input: &dyn Any) -> Option<usize> {
+        if implementation == DEFAULT_IMPLEMENTATION {
+            return Day::solve(self, part, input);
+        }
+        let Some(input) = input.downcast_ref::<S::Input>() else {
+            panic!("input of day {} was not parsed by it", Solution::day(self))
+        };
+        let alternative = self.alternatives().iter().find(|alternative| alternative.part == part && alternative.name == implementation)?;
+        return Some((alternative.solve)(input));
+    }
+
+    fn queries(&self) -> &'static [&'static str] {
+        Solution::queries(self)
+    }
+
+    fn query(&self, input: &dyn Any, query: &[&str]) -> Result<String, String> {
+        let Some(input) = input.downcast_ref::<S::Input>() else {
+            panic!("input of day {} was not parsed by it", Solution::day(self))
+        };
+        Solution::query(self, input, query)
+    }
+
+    fn to_json(&self, input: &dyn Any) -> String {
+        let Some(input) = input.downcast_ref::<S::Input>() else {
+            panic!("input of day {} was not parsed by it", Solution::day(self))
+        };
+        serde_json::to_string(input).unwrap()
+    }
+}
+
+pub static DAYS: &[&dyn Day] = &[
+    &Aoc1,
+    &Aoc2,
+    &Aoc3,
+    &Aoc4,
+    &Aoc5,
+    &Aoc6,
+    &Aoc7,
+    &Aoc8,
+];
+
+pub fn find_day(day: usize) -> Option<&'static dyn Day> {
+    DAYS.iter().find(|registered| registered.day() == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fs_util::read_or_panic;
+    use crate::solution::{DEFAULT_IMPLEMENTATION, find_day, DAYS};
+
+    #[test]
+    fn days_are_registered_in_order() {
+        let days: Vec<usize> = DAYS.iter().map(|day| day.day()).collect();
+        assert_eq!(days, (1..=DAYS.len()).collect::<Vec<usize>>())
+    }
+
+    #[test]
+    fn unknown_day_is_not_found() {
+        assert!(find_day(0).is_none());
+        assert!(find_day(DAYS.len() + 1).is_none())
+    }
+
+    #[test]
+    fn one_parsed_input_serves_both_parts() {
+        let day = find_day(2).unwrap();
+        let input = day.parse("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        assert_eq!(day.solve(1, input.as_ref()), Some(1));
+        assert_eq!(day.solve(2, input.as_ref()), Some(48));
+        assert_eq!(day.solve(3, input.as_ref()), None)
+    }
+
+    #[test]
+    fn solves_input_from_reader_and_memory() {
+        let day = find_day(6).unwrap();
+        let input_data = "Time:      7  15   30\nDistance:  9  40  200";
+        let input = day.parse_from(&mut input_data.as_bytes()).unwrap();
+        assert_eq!(day.solve(1, input.as_ref()), Some(288));
+        assert_eq!(day.solve_input(2, input_data), Some(71503))
+    }
+
+    #[test]
+    fn alternatives_are_listed_after_the_default() {
+        let day = find_day(5).unwrap();
+        assert_eq!(day.implementations(1), vec![DEFAULT_IMPLEMENTATION]);
+        assert_eq!(day.implementations(2), vec![DEFAULT_IMPLEMENTATION, "ranges"]);
+        assert_eq!(day.implementations(3), Vec::<&str>::new());
+        let input = day.parse(&read_or_panic("res/aoc5-example.txt"));
+        assert_eq!(day.solve_with(2, "ranges", input.as_ref()), Some(46));
+        assert_eq!(day.solve_with(1, "ranges", input.as_ref()), None)
+    }
+}
@@ -0,0 +1,203 @@
+use std::io;
+use std::path::Path;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::fetch::USER_AGENT;
+use crate::history::{append_entry, read_entries};
+
+/// Local, not checked in log of all submitted answers, one JSON object per line.
+pub const HISTORY_FILE: &str = ".aoc-history.jsonl";
+
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Feedback {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    RateLimited { wait_seconds: u64 },
+    WrongLevel,
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Attempt {
+    pub day: usize,
+    pub part: usize,
+    pub answer: usize,
+    pub submitted_at: u64,
+    pub feedback: Feedback,
+}
+
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn read(file: &Path) -> io::Result<History> {
+        return Ok(History {
+            attempts: read_entries(file)?
+        });
+    }
+
+    pub fn append(&mut self, file: &Path, attempt: Attempt) -> io::Result<()> {
+        append_entry(file, &attempt)?;
+        self.attempts.push(attempt);
+        return Ok(());
+    }
+
+    /// Returns why `answer` does not need to be sent to the server, if earlier attempts already decide it.
+    pub fn rule_out(&self, day: usize, part: usize, answer: usize, now: u64) -> Option<String> {
+        let attempts: Vec<&Attempt> = self.attempts.iter().filter(|attempt| attempt.day == day && attempt.part == part).collect();
+        for attempt in &attempts {
+            match attempt.feedback {
+                Feedback::Correct if attempt.answer == answer =>
+                    return Some(format!("{answer} is already known to be correct")),
+                Feedback::Correct =>
+                    return Some(format!("the correct answer {} is already known", attempt.answer)),
+                Feedback::RateLimited { wait_seconds } if attempt.submitted_at + wait_seconds > now =>
+                    return Some(format!("rate limited for another {}s", attempt.submitted_at + wait_seconds - now)),
+                Feedback::RateLimited { .. } | Feedback::WrongLevel | Feedback::Unknown => {}
+                _ if attempt.answer == answer =>
+                    return Some(format!("{answer} was already submitted")),
+                Feedback::TooHigh if answer > attempt.answer =>
+                    return Some(format!("{answer} is higher than {}, which is too high", attempt.answer)),
+                Feedback::TooLow if answer < attempt.answer =>
+                    return Some(format!("{answer} is lower than {}, which is too low", attempt.answer)),
+                _ => {}
+            }
+        }
+        return None;
+    }
+}
+
+static WAIT_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"You have (?:(?<minutes>\d+)m\s*)?(?:(?<seconds>\d+)s\s*)?left to wait").unwrap());
+
+pub fn parse_feedback(response: &str) -> Feedback {
+    if response.contains("That's the right answer") {
+        Feedback::Correct
+    } else if response.contains("your answer is too high") {
+        Feedback::TooHigh
+    } else if response.contains("your answer is too low") {
+        Feedback::TooLow
+    } else if response.contains("That's not the right answer") {
+        Feedback::Wrong
+    } else if response.contains("You gave an answer too recently") {
+        let wait_seconds = WAIT_REGEX.captures(response).map(|matched_wait| {
+            let minutes: u64 = matched_wait.name("minutes").map_or(0, |minutes| minutes.as_str().parse().unwrap());
+            let seconds: u64 = matched_wait.name("seconds").map_or(0, |seconds| seconds.as_str().parse().unwrap());
+            minutes * 60 + seconds
+        }).unwrap_or(60);
+        Feedback::RateLimited { wait_seconds }
+    } else if response.contains("You don't seem to be solving the right level") {
+        Feedback::WrongLevel
+    } else {
+        Feedback::Unknown
+    }
+}
+
+pub fn post_answer(config: &Config, day: usize, part: usize, answer: usize) -> io::Result<Feedback> {
+    let Some(session) = &config.session else {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no session configured"));
+    };
+    let url = format!("{}/2023/day/{day}/answer", config.base_url());
+    let response = ureq::post(&url)
+        .header("Cookie", format!("session={session}"))
+        .header("User-Agent", USER_AGENT)
+        .send_form([("level", part.to_string()), ("answer", answer.to_string())])
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|error| io::Error::other(format!("cannot submit to {url}: {error}")))?;
+    return Ok(parse_feedback(&response));
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum SubmitOutcome {
+    RuledOut(String),
+    Submitted(Feedback),
+}
+
+/// Submits `answer` unless the history rules it out, and records every submission in the history file.
+pub fn submit(config: &Config, history_file: &Path, day: usize, part: usize, answer: usize, now: u64) -> io::Result<SubmitOutcome> {
+    let mut history = History::read(history_file)?;
+    if let Some(reason) = history.rule_out(day, part, answer, now) {
+        return Ok(SubmitOutcome::RuledOut(reason));
+    }
+    let feedback = post_answer(config, day, part, answer)?;
+    history.append(history_file, Attempt {
+        day,
+        part,
+        answer,
+        submitted_at: now,
+        feedback,
+    })?;
+    return Ok(SubmitOutcome::Submitted(feedback));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::Config;
+    use crate::submit::{Attempt, Feedback, History, parse_feedback, submit, SubmitOutcome};
+    use crate::test_util::{MockServer, temp_dir};
+
+    fn attempt(answer: usize, submitted_at: u64, feedback: Feedback) -> Attempt {
+        Attempt {
+            day: 5,
+            part: 2,
+            answer,
+            submitted_at,
+            feedback,
+        }
+    }
+
+    #[test]
+    fn parses_server_responses() {
+        assert_eq!(parse_feedback("<p>That's the right answer! You are one gold star closer.</p>"), Feedback::Correct);
+        assert_eq!(parse_feedback("<p>That's not the right answer; your answer is too high.</p>"), Feedback::TooHigh);
+        assert_eq!(parse_feedback("<p>That's not the right answer; your answer is too low.</p>"), Feedback::TooLow);
+        assert_eq!(parse_feedback("<p>That's not the right answer. If you're stuck...</p>"), Feedback::Wrong);
+        assert_eq!(parse_feedback("<p>You gave an answer too recently; You have 4m 59s left to wait.</p>"),
+                   Feedback::RateLimited { wait_seconds: 299 });
+        assert_eq!(parse_feedback("<p>You gave an answer too recently; You have 35s left to wait.</p>"),
+                   Feedback::RateLimited { wait_seconds: 35 })
+    }
+
+    #[test]
+    fn history_rules_out_known_answers() {
+        let history = History {
+            attempts: vec![attempt(100, 0, Feedback::TooHigh), attempt(20, 10, Feedback::TooLow), attempt(50, 20, Feedback::Wrong)],
+        };
+        assert!(history.rule_out(5, 2, 101, 100).is_some());
+        assert!(history.rule_out(5, 2, 19, 100).is_some());
+        assert!(history.rule_out(5, 2, 50, 100).is_some());
+        assert!(history.rule_out(5, 2, 60, 100).is_none());
+        assert!(history.rule_out(5, 1, 101, 100).is_none());
+
+        let rate_limited = History {
+            attempts: vec![attempt(60, 100, Feedback::RateLimited { wait_seconds: 30 })],
+        };
+        assert!(rate_limited.rule_out(5, 2, 60, 120).is_some());
+        assert!(rate_limited.rule_out(5, 2, 60, 130).is_none())
+    }
+
+    #[test]
+    fn submits_and_records_attempts() {
+        let server = MockServer::start(vec![(200, "<article><p>That's not the right answer; your answer is too low.</p></article>")]);
+        let config = Config {
+            session: Some("secret".to_string()),
+            base_url: Some(server.base_url.clone()),
+            ..Config::default()
+        };
+        let history_file = temp_dir("submit").join("history.jsonl");
+
+        assert_eq!(submit(&config, &history_file, 5, 2, 42, 1000).unwrap(), SubmitOutcome::Submitted(Feedback::TooLow));
+        assert!(matches!(submit(&config, &history_file, 5, 2, 41, 1001).unwrap(), SubmitOutcome::RuledOut(_)));
+        assert_eq!(History::read(&history_file).unwrap().attempts, vec![attempt(42, 1000, Feedback::TooLow)]);
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2023/day/5/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=42"))
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use std::thread::JoinHandle;
+
+use crate::answers::read_answers;
+use crate::fs_util::read_or_panic;
+use crate::inputs::{DEFAULT_INPUT_DIR, InputKind, InputResolver};
+
+/// Minimal HTTP server that answers one connection per given response and records the raw requests.
+pub struct MockServer {
+    pub base_url: String,
+    handle: JoinHandle<Vec<String>>,
+}
+
+impl MockServer {
+    pub fn start(responses: Vec<(u16, &str)>) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let responses: Vec<(u16, String)> = responses.into_iter().map(|(status, body)| (status, body.to_string())).collect();
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = length.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut request_body = vec![0; content_length];
+                reader.read_exact(&mut request_body).unwrap();
+                request.push_str(&String::from_utf8(request_body).unwrap());
+                requests.push(request);
+                write!(stream, "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}", body.len()).unwrap();
+            }
+            requests
+        });
+        MockServer {
+            base_url,
+            handle,
+        }
+    }
+
+    /// Waits until all responses were served and returns the received requests.
+    pub fn requests(self) -> Vec<String> {
+        self.handle.join().unwrap()
+    }
+}
+
+pub fn temp_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    return dir;
+}
+
+/// Resolves the inputs of the day tests in the checked in `res` directory, independent of `AOC_INPUT_DIR` and the configuration.
+pub fn test_resolver() -> InputResolver {
+    InputResolver::new(DEFAULT_INPUT_DIR)
+}
+
+/// Example input of a day part, the part specific one if there is one.
+pub fn example_input(day: usize, part: usize) -> String {
+    read_or_panic(test_resolver().resolve(day, InputKind::Example { part }).unwrap_or_else(|error| panic!("{error}")))
+}
+
+/// Expected answer of the example input of a day part, as extracted from the puzzle description.
+pub fn example_answer(day: usize, part: usize) -> usize {
+    read_answers(test_resolver().example_answers_path()).unwrap().expected(day, part).unwrap_or_else(|| panic!("no example answer for {day}.{part}"))
+}
@@ -0,0 +1,163 @@
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Map, Value};
+
+/// Bit `N` is set while the solver of day `N` traces. Checking it is all that disabled tracing costs.
+static TRACED_DAYS: AtomicU32 = AtomicU32::new(0);
+
+static TRACER: Mutex<Option<Tracer>> = Mutex::new(None);
+
+static NEXT_THREAD: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD: u64 = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+struct Tracer {
+    start: Instant,
+    print: bool,
+    events: Option<Vec<TraceEvent>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraceEvent {
+    pub day: usize,
+    pub name: String,
+    pub fields: Vec<(&'static str, String)>,
+    pub thread: u64,
+    pub start: Duration,
+    /// Set for spans, events without duration are instants.
+    pub duration: Option<Duration>,
+}
+
+/// Records `name` with the given `key = value` fields if tracing is enabled for `day`. The fields are not evaluated otherwise.
+macro_rules! trace_event {
+    ($day:expr, $name:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled($day) {
+            $crate::trace::record($day, $name, vec![$((stringify!($key), $value.to_string())),*]);
+        }
+    };
+}
+
+pub(crate) use trace_event;
+
+pub fn enabled(day: usize) -> bool {
+    TRACED_DAYS.load(Ordering::Relaxed) & (1 << day) != 0
+}
+
+/// Enables tracing of `days`, printing each event to stderr if `print` is set and keeping them for [finish] if `collect` is set.
+pub fn start(days: &[usize], print: bool, collect: bool) {
+    *TRACER.lock().unwrap() = Some(Tracer {
+        start: Instant::now(),
+        print,
+        events: collect.then(Vec::new),
+    });
+    TRACED_DAYS.store(days.iter().fold(0, |mask, day| mask | (1 << day)), Ordering::Relaxed);
+}
+
+/// Disables tracing and returns the collected events.
+pub fn finish() -> Vec<TraceEvent> {
+    TRACED_DAYS.store(0, Ordering::Relaxed);
+    TRACER.lock().unwrap().take().and_then(|tracer| tracer.events).unwrap_or_default()
+}
+
+pub fn record(day: usize, name: &str, fields: Vec<(&'static str, String)>) {
+    add(day, name, fields, None, None);
+}
+
+/// Records a span that began at `start` and ends now, used for the phases around the solvers.
+pub fn record_span(day: usize, name: &str, start: Instant) {
+    add(day, name, Vec::new(), Some(start), Some(start.elapsed()));
+}
+
+fn add(day: usize, name: &str, fields: Vec<(&'static str, String)>, start: Option<Instant>, duration: Option<Duration>) {
+    let mut tracer = TRACER.lock().unwrap();
+    let Some(tracer) = tracer.as_mut() else {
+        return;
+    };
+    let event = TraceEvent {
+        day,
+        name: name.to_string(),
+        fields,
+        thread: THREAD.with(|thread| *thread),
+        start: start.unwrap_or_else(Instant::now).saturating_duration_since(tracer.start),
+        duration,
+    };
+    if tracer.print {
+        let fields: String = event.fields.iter().map(|(key, value)| format!(" {key}={value}")).collect();
+        let duration = event.duration.map(|duration| format!(" took={duration:.2?}")).unwrap_or_default();
+        eprintln!("[aoc{day} {:.3?}] {}{fields}{duration}", event.start, event.name);
+    }
+    if let Some(events) = tracer.events.as_mut() {
+        events.push(event);
+    }
+}
+
+/// Formats the events in the Chrome trace event format, viewable in `chrome://tracing` or Perfetto.
+pub fn chrome_trace_json(events: &[TraceEvent]) -> String {
+    let trace_events: Vec<Value> = events.iter().map(|event| {
+        let args: Map<String, Value> = event.fields.iter().map(|(key, value)| (key.to_string(), Value::from(value.as_str()))).collect();
+        let mut trace_event = json!({
+            "name": event.name,
+            "cat": format!("aoc{}", event.day),
+            "pid": 1,
+            "tid": event.thread,
+            "ts": event.start.as_micros() as u64,
+            "args": args,
+        });
+        match event.duration {
+            Some(duration) => {
+                trace_event["ph"] = json!("X");
+                trace_event["dur"] = json!(duration.as_micros() as u64);
+            }
+            None => {
+                trace_event["ph"] = json!("i");
+                trace_event["s"] = json!("t");
+            }
+        }
+        trace_event
+    }).collect();
+    return serde_json::to_string_pretty(&json!({ "traceEvents": trace_events })).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use crate::trace::{chrome_trace_json, enabled, finish, start, TraceEvent};
+
+    fn not_evaluated() -> usize {
+        panic!("fields of disabled days are not evaluated")
+    }
+
+    #[test]
+    fn collects_events_of_enabled_days_only() {
+        start(&[25], false, true);
+        trace_event!(25, "probe", candidate = 7, distance = "far");
+        trace_event!(24, "ignored", value = not_evaluated());
+        let events = finish();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].fields, vec![("candidate", "7".to_string()), ("distance", "far".to_string())]);
+        assert!(!enabled(25))
+    }
+
+    #[test]
+    fn exports_instants_and_spans() {
+        let events = vec![
+            TraceEvent { day: 5, name: "hop".to_string(), fields: vec![("category", "soil".to_string())], thread: 2, start: Duration::from_micros(10), duration: None },
+            TraceEvent { day: 5, name: "part 1".to_string(), fields: vec![], thread: 2, start: Duration::from_micros(5), duration: Some(Duration::from_micros(20)) },
+        ];
+        let trace: Value = serde_json::from_str(&chrome_trace_json(&events)).unwrap();
+        let trace_events = trace["traceEvents"].as_array().unwrap();
+        assert_eq!(trace_events[0]["ph"], "i");
+        assert_eq!(trace_events[0]["cat"], "aoc5");
+        assert_eq!(trace_events[0]["args"]["category"], "soil");
+        assert_eq!(trace_events[1]["ph"], "X");
+        assert_eq!(trace_events[1]["ts"], 5);
+        assert_eq!(trace_events[1]["dur"], 20)
+    }
+}
@@ -0,0 +1,132 @@
+//! JavaScript bindings of the `wasm32-unknown-unknown` build, so that a web page can solve pasted inputs:
+//!
+//! ```text
+//! cargo rustc --release --lib --crate-type cdylib --features wasm --target wasm32-unknown-unknown
+//! wasm-bindgen --target web --out-dir web target/wasm32-unknown-unknown/release/advent_of_code_2023.wasm
+//! ```
+//!
+//! Every day's parser is exported as `parse_dayN`, returning the parsed model as JSON, while [parse] keeps the
+//! parsed input on the Rust side for solving and querying it.
+//!
+//! Without the `wasm` feature the functions are plain Rust functions. The WebAssembly build cannot unwind, so an
+//! input the parser cannot handle aborts the call with a `RuntimeError` instead of an error message.
+
+use std::any::Any;
+
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::solution::{Day, find_day, PARTS};
+
+fn day_or_error(day: usize) -> Result<&'static dyn Day, String> {
+    find_day(day).ok_or_else(|| format!("there is no solution for day {day}"))
+}
+
+/// Solves a part of a day on the puzzle input and returns the answer as text.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn solve(day: usize, part: usize, input: &str) -> Result<String, String> {
+    parse(day, input)?.solve(part)
+}
+
+/// Parses the puzzle input of a day once, so that both parts and the queries can use it. Every day of
+/// [crate::solution::DAYS] is available.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub fn parse(day: usize, input: &str) -> Result<ParsedInput, String> {
+    let day = day_or_error(day)?;
+    Ok(ParsedInput {
+        day,
+        input: day.parse(input),
+    })
+}
+
+/// Exports the parser of every listed day as `parse_dayN`, which returns the parsed input as JSON. New days are
+/// added by `scaffold`.
+macro_rules! day_parsers {
+    ($($day:literal => $name:ident,)*) => {
+        $(
+            #[doc = concat!("Parses the puzzle input of day ", $day, " and returns its model as JSON.")]
+            #[cfg_attr(feature = "wasm", wasm_bindgen)]
+            pub fn $name(input: &str) -> String {
+                parse($day, input).unwrap().model()
+            }
+        )*
+
+        #[cfg(test)]
+        const PARSED_DAYS: &[usize] = &[$($day),*];
+    };
+}
+
+day_parsers! {
+    1 => parse_day1,
+    2 => parse_day2,
+    3 => parse_day3,
+    4 => parse_day4,
+    5 => parse_day5,
+    6 => parse_day6,
+    7 => parse_day7,
+    8 => parse_day8,
+}
+
+/// Parsed puzzle input of one day, kept on the Rust side.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+pub struct ParsedInput {
+    day: &'static dyn Day,
+    input: Box<dyn Any>,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl ParsedInput {
+    pub fn day(&self) -> usize {
+        self.day.day()
+    }
+
+    pub fn solve(&self, part: usize) -> Result<String, String> {
+        if !PARTS.contains(&part) {
+            return Err(format!("there is no part {part}"));
+        }
+        Ok(self.day.solve(part, self.input.as_ref()).unwrap().to_string())
+    }
+
+    /// The parsed input as JSON.
+    pub fn model(&self) -> String {
+        self.day.to_json(self.input.as_ref())
+    }
+
+    /// Usage of the queries of the day, as listed by the REPL.
+    pub fn queries(&self) -> Vec<String> {
+        self.day.queries().iter().map(|query| query.to_string()).collect()
+    }
+
+    /// Answers a REPL query like `location 79`.
+    pub fn query(&self, query: &str) -> Result<String, String> {
+        let words: Vec<&str> = query.split_whitespace().collect();
+        if words.is_empty() {
+            return Err("empty query".to_string());
+        }
+        self.day.query(self.input.as_ref(), &words)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fs_util::read_or_panic;
+    use crate::solution::DAYS;
+    use crate::wasm::{parse, parse_day6, PARSED_DAYS, solve};
+
+    #[test]
+    fn solves_inputs_given_as_text() {
+        assert_eq!(solve(7, 2, &read_or_panic("res/aoc7-example.txt")), Ok("5905".to_string()));
+        assert!(solve(26, 1, "").is_err());
+        let almanac = parse(5, &read_or_panic("res/aoc5-example.txt")).unwrap();
+        assert_eq!(almanac.solve(1), Ok("35".to_string()));
+        assert_eq!(almanac.query("location 79"), Ok("82".to_string()));
+        assert!(almanac.solve(3).is_err())
+    }
+
+    #[test]
+    fn exports_parser_of_every_day() {
+        assert_eq!(PARSED_DAYS, DAYS.iter().map(|day| day.day()).collect::<Vec<usize>>());
+        assert_eq!(parse_day6(&read_or_panic("res/aoc6-example.txt")),
+                   r#"[{"time":7,"record_distance":9},{"time":15,"record_distance":40},{"time":30,"record_distance":200}]"#)
+    }
+}
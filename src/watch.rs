@@ -0,0 +1,108 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use regex::Regex;
+
+/// Polls the module and the input files of one day for changes.
+pub struct Watcher {
+    root: PathBuf,
+    input_dir: PathBuf,
+    day: usize,
+    input_file_regex: Regex,
+    last_seen: Option<Vec<(PathBuf, SystemTime)>>,
+}
+
+impl Watcher {
+    pub fn new(root: &Path, input_dir: &Path, day: usize) -> Watcher {
+        Watcher {
+            root: root.to_path_buf(),
+            input_dir: input_dir.to_path_buf(),
+            day,
+            input_file_regex: Regex::new(&format!(r"^aoc{day}(-.*)?\.txt$")).unwrap(),
+            last_seen: None,
+        }
+    }
+
+    /// `src/aocN.rs` and all `aocN*.txt` files of the input directory that currently exist, sorted by path.
+    pub fn watched_files(&self) -> io::Result<Vec<PathBuf>> {
+        let mut files = vec![self.root.join(format!("src/aoc{}.rs", self.day))];
+        for entry in fs::read_dir(&self.input_dir)? {
+            let path = entry?.path();
+            if path.file_name().and_then(|name| name.to_str()).is_some_and(|name| self.input_file_regex.is_match(name)) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        files.retain(|file| file.exists());
+        return Ok(files);
+    }
+
+    /// Returns whether a watched file was added, removed or modified since the last poll. The first poll always reports a change.
+    pub fn poll(&mut self) -> io::Result<bool> {
+        let seen = self.watched_files()?.into_iter().map(|file| {
+            let modified = fs::metadata(&file)?.modified()?;
+            Ok((file, modified))
+        }).collect::<io::Result<Vec<(PathBuf, SystemTime)>>>()?;
+        let changed = self.last_seen.as_ref() != Some(&seen);
+        self.last_seen = Some(seen);
+        return Ok(changed);
+    }
+}
+
+/// Re-runs the example tests and the real input of `day` through cargo whenever its files change.
+/// Cargo is needed because changes to the source only take effect after rebuilding. The real input
+/// is stopped after `timeout`, so that a slow part does not keep the watch from seeing later changes.
+pub fn watch_day(input_dir: &Path, day: usize, interval: Duration, timeout: Duration) -> io::Result<()> {
+    let mut watcher = Watcher::new(Path::new("."), input_dir, day);
+    loop {
+        if watcher.poll()? {
+            println!("==> AoC {day}: example tests");
+            Command::new("cargo").args(["test", "--quiet", "--lib", &format!("aoc{day}::")]).status()?;
+            println!("==> AoC {day}: real input");
+            Command::new("cargo").args(["run", "--release", "--quiet", "--", "run", "--format", "timed", "--day", &day.to_string()])
+                .args(["--timeout", &timeout.as_secs().to_string()])
+                .arg("--input-dir").arg(input_dir).status()?;
+            println!("==> waiting for changes of {}", watcher.watched_files()?.iter().map(|file| file.display().to_string()).collect::<Vec<String>>().join(", "));
+        }
+        thread::sleep(interval);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::fs::File;
+    use std::time::{Duration, SystemTime};
+
+    use crate::test_util::temp_dir;
+    use crate::watch::Watcher;
+
+    #[test]
+    fn polls_module_and_input_files_of_day() {
+        let root = temp_dir("watch");
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("res")).unwrap();
+        for file in ["src/aoc1.rs", "res/aoc1.txt", "res/aoc10.txt", "res/answers.toml"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let mut watcher = Watcher::new(&root, &root.join("res"), 1);
+        assert_eq!(watcher.watched_files().unwrap(), vec![root.join("res/aoc1.txt"), root.join("src/aoc1.rs")]);
+        assert!(watcher.poll().unwrap());
+        assert!(!watcher.poll().unwrap());
+
+        fs::write(root.join("res/aoc10.txt"), "other day").unwrap();
+        assert!(!watcher.poll().unwrap());
+
+        File::options().write(true).open(root.join("src/aoc1.rs")).unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+        assert!(watcher.poll().unwrap());
+
+        fs::write(root.join("res/aoc1-2-example.txt"), "").unwrap();
+        assert!(watcher.poll().unwrap());
+        assert!(!watcher.poll().unwrap())
+    }
+}